@@ -0,0 +1,135 @@
+use crate::{
+    config::{Args, Command, Config, ContrastArgs, NearestArgs, TemplateOptions},
+    generate, utils,
+};
+use anyhow::{Context, Result};
+use clap::Parser;
+use heck::ToSnakeCase;
+use palette::{Color, PaletteFile};
+use std::{fs, path::Path};
+
+/// Runs `rose-pine-build` with the process arguments
+pub fn run() -> Result<()> {
+    let args = Args::parse();
+    let config = Config::try_from(TemplateOptions::try_from(&args)?)?;
+
+    match args.command {
+        Some(Command::Contrast(ref contrast_args)) => {
+            return check_contrast(contrast_args, &config)
+        }
+        Some(Command::Nearest(ref nearest_args)) => return find_nearest(nearest_args, &config),
+        Some(Command::Schema) => {
+            println!("{:#}", PaletteFile::json_schema());
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let template_source = args
+        .template_source
+        .as_deref()
+        .context("expected a template source")?;
+    if !template_source.exists() {
+        anyhow::bail!("template source can't be found at path {template_source:?}")
+    }
+
+    _ = fs::create_dir_all(&args.out);
+    if template_source.is_dir() {
+        generate_directory(
+            template_source,
+            template_source,
+            &args.out,
+            args.recurse,
+            &config,
+        )?;
+    } else {
+        let filetype = template_source.extension().map_or("".into(), |s| {
+            format!(".{}", s.to_str().expect("valid string"))
+        });
+
+        let variants = generate::generate_template(template_source, &config)?;
+        for (variant, content) in variants {
+            let filename = format!("{}{}", variant.key, filetype);
+            let path = args.out.join(filename);
+            fs::write(path, content)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn check_contrast(args: &ContrastArgs, config: &Config) -> Result<()> {
+    let checks = args.load_policy()?.evaluate(&config.variants)?;
+    // tables end with a newline already, json doesn't
+    println!("{}", args.output.render(&checks)?.trim_end_matches('\n'));
+
+    let failed = checks.iter().filter(|check| !check.pass).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} contrast checks failed", checks.len())
+    }
+
+    Ok(())
+}
+
+fn find_nearest(args: &NearestArgs, config: &Config) -> Result<()> {
+    let mut rows = vec![];
+    for value in &args.colors {
+        let color = Color::parse(value)?.rgb;
+        for variant in &config.variants {
+            let (role, distance) = variant.nearest_role_by(color, args.metric);
+            rows.push([
+                format!("#{}", color.hex()),
+                variant.key.clone(),
+                role.to_string().to_snake_case(),
+                format!("#{}", variant.get_rgb(role).hex()),
+                format!("{distance:.3}"),
+            ]);
+        }
+    }
+
+    let header = ["color", "variant", "role", "role color", "distance"];
+    print!("{}", utils::table(header, &rows, false));
+
+    Ok(())
+}
+
+fn generate_directory(
+    base_path: &Path,
+    directory_path: &Path,
+    out_path: &Path,
+    recurse: bool,
+    config: &Config,
+) -> Result<()> {
+    for entry in fs::read_dir(directory_path)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            if !recurse {
+                continue;
+            }
+
+            generate_directory(
+                base_path,
+                &directory_path.join(path.file_name().context("expected a dirname")?),
+                out_path,
+                recurse,
+                config,
+            )?;
+        } else {
+            for (variant, content) in generate::generate_template(&path, config)? {
+                let path = out_path
+                    .join(&variant.key)
+                    .join(path.strip_prefix(base_path)?);
+
+                _ = fs::create_dir_all(
+                    path.parent()
+                        .context("expected file to have parent directory")?,
+                );
+
+                fs::write(path, content)?;
+            }
+        }
+    }
+
+    Ok(())
+}
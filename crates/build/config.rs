@@ -1,17 +1,29 @@
 use crate::{
+    contrast,
     format::Format,
     generate::{self},
     parse::{self, Delimiter},
+    utils::{Case, Casing},
 };
 
+use anyhow::{anyhow, Context, Result};
 use clap::{
     builder::{styling::AnsiColor, Styles},
-    Parser,
+    Parser, Subcommand,
+};
+use palette::{
+    Alias, ContrastTargets, Deficiency, DeltaE, Interpolation, Metadata, PaletteFile,
+    PaletteVersion, Role, RoleCategory, VariantDefinition,
 };
 use serde::Serialize;
-use std::path::PathBuf;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
 
-#[derive(Clone, Copy, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Config {
     /// use tera templating engine
     #[cfg(feature = "templating")]
@@ -19,6 +31,272 @@ pub struct Config {
 
     pub parse: parse::ParseOptions,
     pub generate: generate::Options,
+
+    /// variants to generate templates for
+    pub variants: Vec<VariantDefinition>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "templating")]
+            tera: false,
+            parse: parse::ParseOptions::default(),
+            generate: generate::Options::default(),
+            variants: PaletteFile::builtin().variants,
+        }
+    }
+}
+
+/// Everything a [`Config`] is built from, filled in from the command line or by the python bindings
+#[derive(Clone, Debug, Default)]
+pub struct TemplateOptions {
+    #[cfg(feature = "templating")]
+    pub tera: bool,
+    pub parse: parse::ParseOptions,
+    pub generate: generate::Options,
+
+    /// toml or json palette file replacing the built-in variants
+    pub palette: Option<PathBuf>,
+    /// revision of the built-in palette, can't be combined with `palette`
+    pub palette_version: Option<PaletteVersion>,
+    /// `name=role[/alpha]` alias definitions
+    pub aliases: Vec<String>,
+    /// `key=from:to:ratio[:space]` interpolated variants
+    pub interpolate: Vec<String>,
+    /// `name=value` role definitions, which can refer to aliases, interpolated variants and earlier roles
+    pub roles: Vec<String>,
+    /// targets of high contrast variants, which are only generated when set
+    pub high_contrast: Option<ContrastTargets>,
+    /// deficiencies to generate simulated variants for
+    pub simulate: Vec<Deficiency>,
+}
+
+impl TryFrom<TemplateOptions> for Config {
+    type Error = anyhow::Error;
+
+    fn try_from(value: TemplateOptions) -> Result<Self> {
+        if value.palette.is_some() && value.palette_version.is_some() {
+            anyhow::bail!("a palette file and a palette version can't be used together")
+        }
+
+        let mut config = Config::with_palette(value.palette.as_deref(), value.palette_version)?;
+        #[cfg(feature = "templating")]
+        {
+            config.tera = value.tera;
+        }
+        config.parse = value.parse;
+        config.generate = value.generate;
+
+        for definition in &value.aliases {
+            config.define_alias(definition)?;
+        }
+        for definition in &value.interpolate {
+            config.interpolate(definition)?;
+        }
+        for definition in &value.roles {
+            config.define_role(definition)?;
+        }
+        if let Some(targets) = value.high_contrast {
+            config.high_contrast(&targets);
+        }
+        config.simulate(&value.simulate);
+
+        Ok(config)
+    }
+}
+
+impl Config {
+    /// Default options for the variants of a palette file, or of a revision of the built-in palette
+    fn with_palette(path: Option<&Path>, version: Option<PaletteVersion>) -> Result<Self> {
+        let variants = match path {
+            Some(path) => {
+                let content = fs::read_to_string(path)
+                    .with_context(|| format!("unable to read palette file at path {path:?}"))?;
+                let palette = match path.extension().and_then(|ext| ext.to_str()) {
+                    Some("json") => PaletteFile::from_json(&content)?,
+                    _ => PaletteFile::from_toml(&content)?,
+                };
+                if palette.variants.is_empty() {
+                    anyhow::bail!("palette file at path {path:?} doesn't define any variants")
+                }
+                palette.variants
+            }
+            None => PaletteFile::builtin_version(version.unwrap_or_default()).variants,
+        };
+
+        let config = Self {
+            variants,
+            ..Self::default()
+        };
+        config.validate_roles()?;
+        Ok(config)
+    }
+
+    /// Evaluates a `name=value` role definition for every variant
+    pub fn define_role(&mut self, definition: &str) -> Result<()> {
+        let (name, value) = definition
+            .split_once('=')
+            .context("expected role definition in the form of `name=value`")?;
+        let name = name.trim();
+        validate_role_name(name)?;
+        if self.variants.iter().any(|v| v.aliases.contains_key(name)) {
+            anyhow::bail!("role name `{name}` is already in use by an alias")
+        }
+
+        let roles = parse::parse_role_expression(value.trim(), self)
+            .map_err(|e| anyhow!("invalid value for role `{name}`: {e:?}"))?;
+        for variant in &mut self.variants {
+            let color = roles.get_color(variant);
+            variant.roles.insert(name.to_string(), color.rgb);
+        }
+
+        Ok(())
+    }
+
+    /// Overrides or adds a `name=role[/alpha]` alias for every variant
+    pub fn define_alias(&mut self, definition: &str) -> Result<()> {
+        let (name, value) = definition
+            .split_once('=')
+            .context("expected alias definition in the form of `name=role[/alpha]`")?;
+        let name = name.trim();
+        validate_role_name(name)?;
+        if self.variants.iter().any(|v| v.roles.contains_key(name)) {
+            anyhow::bail!("alias name `{name}` is already in use by a custom role")
+        }
+
+        let alias: Alias = value
+            .trim()
+            .parse()
+            .map_err(|e| anyhow!("invalid value for alias `{name}`: {e}"))?;
+        for variant in &mut self.variants {
+            variant.aliases.insert(name.to_string(), alias);
+        }
+
+        Ok(())
+    }
+
+    /// Adds a variant described by `key=from:to:ratio[:space]` between two existing variants
+    pub fn interpolate(&mut self, definition: &str) -> Result<()> {
+        let (key, value) = definition
+            .split_once('=')
+            .context("expected interpolated variant in the form of `key=from:to:ratio[:space]`")?;
+        let key = key.trim();
+        let mut parts = value.split(':').map(str::trim);
+        let (Some(from), Some(to), Some(ratio)) = (parts.next(), parts.next(), parts.next()) else {
+            anyhow::bail!(
+                "expected interpolated variant in the form of `key=from:to:ratio[:space]`"
+            )
+        };
+
+        let find = |key: &str| {
+            self.variants
+                .iter()
+                .find(|v| v.key == key)
+                .with_context(|| format!("unable to interpolate from unknown variant `{key}`"))
+        };
+        let (from, to) = (find(from)?, find(to)?);
+        let ratio: f32 = ratio
+            .parse()
+            .with_context(|| format!("invalid interpolation ratio `{ratio}`"))?;
+        if !(0.0..=100.0).contains(&ratio) {
+            anyhow::bail!("interpolation ratio must be between 0 and 100, got {ratio}")
+        }
+        let space = match parts.next() {
+            Some(space) => Interpolation::from_str(space).map_err(|_| {
+                anyhow!("unknown interpolation space `{space}`, expected srgb, oklab or oklch")
+            })?,
+            None => Interpolation::default(),
+        };
+
+        if key.is_empty() || self.variants.iter().any(|v| v.key == key) {
+            anyhow::bail!("variant key `{key}` is empty or already in use")
+        }
+
+        let variant = from.interpolate(to, ratio, space, key);
+        self.variants.push(variant);
+
+        Ok(())
+    }
+
+    /// Appends a high contrast copy of every variant
+    pub fn high_contrast(&mut self, targets: &ContrastTargets) {
+        let derived = self
+            .variants
+            .iter()
+            .map(|v| v.high_contrast(targets))
+            .collect::<Vec<_>>();
+        self.variants.extend(derived);
+    }
+
+    /// Appends a simulated copy of every variant for each color vision deficiency
+    pub fn simulate(&mut self, deficiencies: &[Deficiency]) {
+        let simulated = deficiencies
+            .iter()
+            .flat_map(|deficiency| self.variants.iter().map(|v| v.simulate(*deficiency)))
+            .collect::<Vec<_>>();
+        self.variants.extend(simulated);
+    }
+
+    /// Ensures custom roles from the palette are valid and defined for every variant
+    fn validate_roles(&self) -> Result<()> {
+        let Some(first) = self.variants.first() else {
+            return Ok(());
+        };
+
+        for name in first.roles.keys().chain(first.aliases.keys()) {
+            validate_role_name(name)?;
+        }
+        if let Some(name) = first.roles.keys().find(|n| first.aliases.contains_key(*n)) {
+            anyhow::bail!("`{name}` is defined as both a custom role and an alias")
+        }
+
+        for variant in &self.variants {
+            if !variant.roles.keys().eq(first.roles.keys()) {
+                anyhow::bail!(
+                    "variants {:?} and {:?} don't define the same custom roles",
+                    first.key,
+                    variant.key
+                )
+            }
+            if !variant.aliases.keys().eq(first.aliases.keys()) {
+                anyhow::bail!(
+                    "variants {:?} and {:?} don't define the same aliases",
+                    first.key,
+                    variant.key
+                )
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Tera context keys next to the roles, besides the plural names of role categories
+const CONTEXT_KEYS: [&str; 3] = ["shades", "aliases", "ansi"];
+
+fn validate_role_name(name: &str) -> Result<()> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        anyhow::bail!("invalid role name `{name}`, expected a snake_case identifier")
+    }
+
+    let reserved = Role::iter()
+        .map(|r| r.to_case(Case::Snake))
+        .chain(Metadata::iter().map(|m| m.to_case(Case::Snake)))
+        .chain(palette::AnsiColor::iter().map(|c| format!("{}{c}", parse::ANSI_PREFIX)))
+        .chain(CONTEXT_KEYS.map(String::from))
+        .chain(RoleCategory::iter().map(RoleCategory::plural));
+    for reserved in reserved {
+        if reserved == name {
+            anyhow::bail!("role name `{name}` is already in use")
+        }
+    }
+
+    Ok(())
 }
 
 fn styles() -> Styles {
@@ -31,8 +309,12 @@ fn styles() -> Styles {
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None, styles = styles())]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 /// Theme generator for Rosé Pine
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// path to directory where generated files will be output
     #[clap(long, short, default_value = "dist")]
     pub out: PathBuf,
@@ -62,17 +344,144 @@ pub struct Args {
     /// always add alpha values
     pub force_alpha: bool,
 
+    #[clap(long, default_value = "0")]
+    /// decimal places for fractional values like hsl
+    pub precision: usize,
+
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "base",
+        value_name = "ROLE"
+    )]
+    /// output opaque colors by compositing alpha values over a role
+    pub flatten: Option<Role>,
+
     #[clap(long, short, default_value = "$")]
     /// variable prefix
     pub prefix: char,
 
+    #[clap(long, global = true)]
+    /// path to a toml or json palette file, replacing the built-in variants
+    pub palette: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "VERSION",
+        conflicts_with = "palette",
+        global = true
+    )]
+    /// revision of the built-in palette, `v1` or the current `v2`, to reproduce older releases
+    pub palette_version: Option<PaletteVersion>,
+
+    #[clap(long = "role", value_name = "NAME=VALUE", global = true)]
+    /// define an additional role as a color or an expression over other roles, e.g. `brand=$(rose|iris|pine)`
+    pub roles: Vec<String>,
+
+    #[clap(long = "alias", value_name = "NAME=ROLE[/ALPHA]", global = true)]
+    /// override or add a semantic alias for a role, e.g. `error=rose` or `selection=iris/20`
+    pub aliases: Vec<String>,
+
+    #[clap(long, value_name = "KEY=FROM:TO:RATIO[:SPACE]", global = true)]
+    /// add a variant between two others, e.g. `dusk=moon:dawn:30:oklch` is 30% of the way from moon to dawn,
+    /// interpolated in oklab by default or in srgb or oklch
+    pub interpolate: Vec<String>,
+
+    #[clap(long, global = true)]
+    /// also generate high contrast variants, meeting wcag contrast targets on base and surface
+    pub high_contrast: bool,
+
+    #[clap(
+        long = "contrast-target",
+        value_name = "GROUP=RATIO",
+        requires = "high_contrast",
+        global = true
+    )]
+    /// minimum contrast in high contrast variants for `text`, `subtle`, `muted` or `accent` roles,
+    /// defaults to text=7, subtle=7, muted=4.5 and accent=4.5
+    pub contrast_targets: Vec<String>,
+
+    #[clap(long, value_name = "DEFICIENCY", value_delimiter = ',', global = true)]
+    /// also generate variants as perceived with a color vision deficiency,
+    /// one of protanopia, deuteranopia, tritanopia or achromatopsia
+    pub simulate: Vec<Deficiency>,
+
     /// path to template file or directory
-    pub template_source: PathBuf,
+    #[clap(required = true)]
+    pub template_source: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Check contrast of role pairs in every variant, failing when a pair is below its threshold
+    Contrast(ContrastArgs),
+    /// Find the role closest to each color in every variant
+    Nearest(NearestArgs),
+    /// Print the json schema of palette files
+    Schema,
 }
 
-impl From<&Args> for Config {
-    fn from(value: &Args) -> Self {
-        Config {
+#[derive(clap::Args)]
+pub struct ContrastArgs {
+    #[clap(long)]
+    /// path to a toml contrast policy, defaults to the built-in policy
+    pub policy: Option<PathBuf>,
+
+    #[clap(long, short, default_value = "table")]
+    /// report format
+    pub output: contrast::Output,
+}
+
+#[derive(clap::Args)]
+pub struct NearestArgs {
+    #[clap(long, short, default_value = "ciede2000")]
+    /// color difference metric, either `ciede2000` or `ok`
+    pub metric: DeltaE,
+
+    #[clap(required = true, value_name = "COLOR")]
+    /// colors to look up in any notation, e.g. `#e0607f`, `rgb(224 96 127)` or `crimson`
+    pub colors: Vec<String>,
+}
+
+impl ContrastArgs {
+    pub fn load_policy(&self) -> Result<contrast::Policy> {
+        let Some(ref path) = self.policy else {
+            return Ok(contrast::Policy::builtin());
+        };
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("unable to read contrast policy at path {path:?}"))?;
+        contrast::Policy::from_toml(&content)
+    }
+}
+
+impl Args {
+    /// Overrides the default high contrast targets with `group=ratio` definitions
+    fn contrast_targets(&self) -> Result<ContrastTargets> {
+        let mut targets = ContrastTargets::default();
+        for definition in &self.contrast_targets {
+            let (group, ratio) = definition
+                .split_once('=')
+                .context("expected contrast target in the form of `group=ratio`")?;
+            let ratio: f32 = ratio
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid contrast ratio `{ratio}`"))?;
+            targets
+                .set(group.trim(), ratio)
+                .map_err(anyhow::Error::msg)?;
+        }
+
+        Ok(targets)
+    }
+}
+
+impl TryFrom<&Args> for TemplateOptions {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &Args) -> Result<Self> {
+        Ok(Self {
             #[cfg(feature = "templating")]
             tera: value.tera,
             parse: parse::ParseOptions {
@@ -84,7 +493,53 @@ impl From<&Args> for Config {
                 format: value.format,
                 strip_spaces: false,
                 force_alpha: value.force_alpha,
+                precision: value.precision,
+                flatten: value.flatten,
             },
-        }
+            palette: value.palette.clone(),
+            palette_version: value.palette_version,
+            aliases: value.aliases.clone(),
+            interpolate: value.interpolate.clone(),
+            roles: value.roles.clone(),
+            high_contrast: match value.high_contrast {
+                true => Some(value.contrast_targets()?),
+                false => None,
+            },
+            simulate: value.simulate.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_arg() {
+        let parse = |args: &[&str]| Args::try_parse_from([&["rose-pine-build"], args].concat());
+
+        // a bare flag doesn't take the template as its role
+        let args = parse(&["--flatten", "template.json"]).unwrap();
+        assert_eq!(args.flatten, Some(Role::Base));
+        assert_eq!(args.template_source, Some("template.json".into()));
+
+        let args = parse(&["--flatten=surface", "template.json"]).unwrap();
+        assert_eq!(args.flatten, Some(Role::Surface));
+        assert_eq!(parse(&["template.json"]).unwrap().flatten, None);
+    }
+
+    #[test]
+    fn contrast_target_arg() {
+        let parse = |args: &[&str]| Args::try_parse_from([&["rose-pine-build"], args].concat());
+
+        assert!(parse(&["--contrast-target", "accent=7", "template.json"]).is_err());
+        let args = parse(&[
+            "--high-contrast",
+            "--contrast-target",
+            "accent=7",
+            "template.json",
+        ])
+        .unwrap();
+        assert_eq!(args.contrast_targets().unwrap().accent, 7.0);
     }
 }
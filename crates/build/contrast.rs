@@ -0,0 +1,227 @@
+use crate::utils;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use palette::{VariantDefinition, WcagLevel};
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+/// Policy checked when no policy file is passed
+const DEFAULT_POLICY: &str = include_str!("contrast.toml");
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    pub pairs: Vec<Requirement>,
+}
+
+/// Minimum contrast of a foreground role on a background role
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Requirement {
+    pub fg: String,
+    pub bg: String,
+    pub alpha: Option<f32>,
+    /// minimum wcag 2.x contrast ratio
+    pub ratio: Option<f32>,
+    /// minimum absolute apca lightness contrast
+    pub apca: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub variant: String,
+    pub fg: String,
+    pub bg: String,
+    pub alpha: Option<f32>,
+    pub ratio: f32,
+    pub apca: f32,
+    pub level: WcagLevel,
+    pub min_ratio: Option<f32>,
+    pub min_apca: Option<f32>,
+    pub pass: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum Output {
+    #[default]
+    Table,
+    Json,
+    Markdown,
+}
+
+impl Policy {
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let policy: Policy = toml::from_str(content)?;
+        for pair in &policy.pairs {
+            if pair.ratio.is_none() && pair.apca.is_none() {
+                anyhow::bail!(
+                    "pair {} on {} requires a minimum `ratio` or `apca` value",
+                    pair.fg,
+                    pair.bg
+                )
+            }
+        }
+
+        Ok(policy)
+    }
+
+    pub fn builtin() -> Self {
+        Self::from_toml(DEFAULT_POLICY).expect("built-in contrast policy is valid")
+    }
+
+    /// Checks every pair against every variant
+    pub fn evaluate(&self, variants: &[VariantDefinition]) -> Result<Vec<Check>> {
+        let mut checks = vec![];
+
+        for variant in variants {
+            for pair in &self.pairs {
+                let find = |name: &str| {
+                    variant.find_color(name).with_context(|| {
+                        format!("unknown role `{name}` in variant {:?}", variant.key)
+                    })
+                };
+                let background = find(&pair.bg)?;
+                let foreground = match pair.alpha {
+                    Some(alpha) => find(&pair.fg)?.flatten(&background, alpha),
+                    None => find(&pair.fg)?,
+                };
+
+                let contrast = foreground.contrast(&background);
+                let pass = pair.ratio.is_none_or(|min| contrast.ratio >= min)
+                    && pair.apca.is_none_or(|min| contrast.apca.abs() >= min);
+
+                checks.push(Check {
+                    variant: variant.key.clone(),
+                    fg: pair.fg.clone(),
+                    bg: pair.bg.clone(),
+                    alpha: pair.alpha,
+                    ratio: contrast.ratio,
+                    apca: contrast.apca,
+                    level: contrast.level,
+                    min_ratio: pair.ratio,
+                    min_apca: pair.apca,
+                    pass,
+                });
+            }
+        }
+
+        Ok(checks)
+    }
+}
+
+impl Output {
+    pub fn render(&self, checks: &[Check]) -> Result<String> {
+        if let Self::Json = self {
+            return Ok(serde_json::to_string_pretty(checks)?);
+        }
+
+        let header = [
+            "variant", "fg", "bg", "alpha", "ratio", "apca", "level", "required", "status",
+        ];
+        let rows = checks
+            .iter()
+            .map(|check| {
+                let required = [
+                    check.min_ratio.map(|min| format!("{min}:1")),
+                    check.min_apca.map(|min| format!("Lc {min}")),
+                ];
+                [
+                    check.variant.clone(),
+                    check.fg.clone(),
+                    check.bg.clone(),
+                    check.alpha.map_or("".into(), |alpha| format!("{alpha}%")),
+                    format!("{:.2}:1", check.ratio),
+                    format!("{:.1}", check.apca),
+                    check.level.to_string(),
+                    required
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    match check.pass {
+                        true => "pass".into(),
+                        false => "fail".into(),
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        Ok(utils::table(header, &rows, matches!(self, Self::Markdown)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use palette::PaletteFile;
+
+    #[test]
+    fn builtin_policy_passes() {
+        let checks = Policy::builtin()
+            .evaluate(&PaletteFile::builtin().variants)
+            .unwrap();
+        assert!(checks.iter().all(|check| check.pass));
+    }
+
+    #[test]
+    fn failing_pairs() {
+        let policy = Policy::from_toml(
+            r#"
+            [[pairs]]
+            fg = "text"
+            bg = "base"
+            ratio = 7.0
+
+            [[pairs]]
+            fg = "love"
+            bg = "base"
+            alpha = 50
+            apca = 30
+            "#,
+        )
+        .unwrap();
+        let checks = policy.evaluate(&PaletteFile::builtin().variants).unwrap();
+
+        let failed = checks
+            .iter()
+            .filter(|check| !check.pass)
+            .map(|check| format!("{} {}", check.variant, check.fg))
+            .collect::<Vec<_>>();
+        assert_eq!(failed, ["main love", "moon love", "dawn text"]);
+    }
+
+    #[test]
+    fn requires_threshold() {
+        assert!(Policy::from_toml("[[pairs]]\nfg = \"text\"\nbg = \"base\"").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        // a misspelled alpha would otherwise measure the pair as opaque
+        let err =
+            Policy::from_toml("[[pairs]]\nfg = \"text\"\nbg = \"base\"\nratio = 7\nalhpa = 20")
+                .unwrap_err();
+        assert!(format!("{err:#}").contains("alhpa"));
+        assert!(
+            Policy::from_toml("pair = []\n[[pairs]]\nfg = \"text\"\nbg = \"base\"\nratio = 7")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        let policy =
+            Policy::from_toml("[[pairs]]\nfg = \"text\"\nbg = \"base\"\nratio = 7").unwrap();
+        let checks = policy
+            .evaluate(&PaletteFile::builtin().variants[..1])
+            .unwrap();
+
+        assert_eq!(
+            Output::Markdown.render(&checks).unwrap(),
+            "| variant | fg   | bg   | alpha | ratio   | apca  | level | required | status |\n\
+             | ------- | ---- | ---- | ----- | ------- | ----- | ----- | -------- | ------ |\n\
+             | main    | text | base |       | 13.39:1 | -86.8 | aaa   | 7:1      | pass   |\n"
+        );
+    }
+}
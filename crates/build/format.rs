@@ -1,5 +1,5 @@
 use clap::ValueEnum;
-use palette::Color;
+use palette::{Color, XtermPalette};
 use serde::Serialize;
 use strum_macros::{Display, EnumIter};
 
@@ -33,6 +33,20 @@ pub enum Format {
     HslFunction,
     /// [2, 55%, 83%]
     HslArray,
+    /// lab(80% 17 8)
+    LabFunction,
+    /// lch(80% 19 24)
+    LchFunction,
+    /// oklab(84% 0.051 0.020)
+    OklabFunction,
+    /// oklch(84% 0.054 21)
+    OklchFunction,
+    /// 181
+    Xterm256,
+    /// 74
+    Xterm88,
+    /// 7
+    Xterm16,
 }
 
 impl Format {
@@ -47,10 +61,43 @@ impl Format {
         matches!(self, Self::Hex | Self::HexNs | Self::Ahex | Self::AhexNs)
     }
 
-    pub fn format_color(&self, color: Color, alpha: Option<impl Into<f32> + Copy>) -> String {
-        let mut chunks = match self.is_hsl() {
-            true => vec![color.hsl.h as f32, color.hsl.s as f32, color.hsl.l as f32],
-            false => vec![color.rgb.r as f32, color.rgb.g as f32, color.rgb.b as f32],
+    /// Css color level 4 functions with space separated components and a `/` before alpha
+    pub fn is_css4(&self) -> bool {
+        matches!(
+            self,
+            Self::LabFunction | Self::LchFunction | Self::OklabFunction | Self::OklchFunction
+        )
+    }
+
+    /// Indexed terminal palette, colors in these formats are quantized to the nearest index and
+    /// have no alpha
+    pub fn xterm_palette(&self) -> Option<XtermPalette> {
+        match self {
+            Self::Xterm256 => Some(XtermPalette::Colors256),
+            Self::Xterm88 => Some(XtermPalette::Colors88),
+            Self::Xterm16 => Some(XtermPalette::Colors16),
+            _ => None,
+        }
+    }
+
+    /// Formats a color, rounding fractional components such as hsl values to `precision` decimals
+    pub fn format_color(
+        &self,
+        color: Color,
+        alpha: Option<impl Into<f32> + Copy>,
+        precision: usize,
+    ) -> String {
+        if let Some(palette) = self.xterm_palette() {
+            return color.xterm_index(palette).to_string();
+        }
+
+        let mut chunks = match self {
+            _ if self.is_hsl() => vec![color.hsl.h, color.hsl.s, color.hsl.l],
+            Self::LabFunction => vec![color.lab.l, color.lab.a, color.lab.b],
+            Self::LchFunction => vec![color.lch.l, color.lch.c, color.lch.h],
+            Self::OklabFunction => vec![color.oklab.l * 100.0, color.oklab.a, color.oklab.b],
+            Self::OklchFunction => vec![color.oklch.l * 100.0, color.oklch.c, color.oklch.h],
+            _ => vec![color.rgb.r as f32, color.rgb.g as f32, color.rgb.b as f32],
         };
 
         if let Some(alpha) = alpha.map(|a| a.into() / 100.0) {
@@ -61,22 +108,26 @@ impl Format {
             }
         }
 
-        let chunks = self.format_chunks(&chunks);
+        let chunks = self.format_chunks(&chunks, precision);
         match self {
             Self::Hex | Self::Ahex => format!("#{chunks}"),
             Self::RgbArray | Self::HslArray => format!("[{chunks}]"),
             Self::RgbFunction => format!("rgb({chunks})",),
             Self::HslFunction => format!("hsl({chunks})"),
+            Self::LabFunction => format!("lab({chunks})"),
+            Self::LchFunction => format!("lch({chunks})"),
+            Self::OklabFunction => format!("oklab({chunks})"),
+            Self::OklchFunction => format!("oklch({chunks})"),
             _ => chunks,
         }
     }
 
     /// Formats and joins all color components
-    fn format_chunks(&self, chunks: &[f32]) -> String {
+    fn format_chunks(&self, chunks: &[f32], precision: usize) -> String {
         let chunks = chunks
             .iter()
             .enumerate()
-            .map(|(i, x)| self.format_chunk(*x, i))
+            .map(|(i, x)| self.format_chunk(*x, i, precision))
             .collect::<Vec<_>>();
         match self {
             Self::Hex | Self::HexNs | Self::Ahex | Self::AhexNs => chunks.join("").to_lowercase(),
@@ -88,13 +139,30 @@ impl Format {
             | Self::HslFunction => chunks.join(", "),
             Self::RgbNs | Self::HslNs => chunks.join(" "),
             Self::RgbAnsi => chunks.join(";"),
+            Self::Xterm256 | Self::Xterm88 | Self::Xterm16 => chunks.concat(),
+            Self::LabFunction | Self::LchFunction | Self::OklabFunction | Self::OklchFunction => {
+                match chunks.split_at(3) {
+                    (components, []) => components.join(" "),
+                    (components, alpha) => format!("{} / {}", components.join(" "), alpha.join("")),
+                }
+            }
         }
     }
 
     /// Formats a single color component
-    fn format_chunk(&self, chunk: f32, i: usize) -> String {
-        if self.is_hsl() && (i > 0 && i < 3) {
-            format!("{chunk}%")
+    fn format_chunk(&self, chunk: f32, i: usize, precision: usize) -> String {
+        if self.is_css4() && i == 0 {
+            format!("{:.precision$}%", round(chunk, precision))
+        } else if matches!(self, Self::OklabFunction) && i < 3
+            || matches!(self, Self::OklchFunction) && i == 1
+        {
+            // oklab components are small fractions which need a few extra decimals
+            let precision = precision + 3;
+            format!("{:.precision$}", round(chunk, precision))
+        } else if self.is_css4() && i < 3 || self.is_hsl() && i == 0 {
+            format!("{:.precision$}", round(chunk, precision))
+        } else if self.is_hsl() && i < 3 {
+            format!("{:.precision$}%", round(chunk, precision))
         } else if self.is_hex() {
             format!("{:02X}", chunk.round() as u8)
         } else {
@@ -103,14 +171,22 @@ impl Format {
     }
 }
 
+/// Rounds half away from zero, unlike the round half to even of float formatting
+fn round(value: f32, precision: usize) -> f32 {
+    let factor = 10f32.powi(precision as i32);
+    (value * factor).round() / factor
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use palette::{Role, Variant};
+    use palette::{ParseColorOptions, Rgb, Role, Variant};
+    use proptest::prelude::*;
+    use strum::IntoEnumIterator;
 
     fn assert_format(format: Format, alpha: Option<f32>, val: &str) {
         let color = Role::Love.get_color(&Variant::Moon);
-        assert_eq!(val, format.format_color(color, alpha));
+        assert_eq!(val, format.format_color(color, alpha, 0));
     }
 
     #[test]
@@ -127,6 +203,27 @@ mod tests {
         assert_format(Format::HslFunction, Some(70.0), "hsl(343, 76%, 68%, 0.7)");
     }
 
+    #[test]
+    fn format_hsl_precision() {
+        let color = Role::Love.get_color(&Variant::Moon);
+        assert_eq!(
+            Format::HslFunction.format_color(color.clone(), None::<f32>, 1),
+            "hsl(343.1, 75.6%, 67.8%)"
+        );
+        assert_eq!(
+            Format::HslNs.format_color(color, Some(50.0), 2),
+            "343.06 75.61% 67.84% 0.5"
+        );
+    }
+
+    #[test]
+    fn format_css4() {
+        assert_format(Format::LabFunction, None, "lab(63% 51 5)");
+        assert_format(Format::LchFunction, Some(50.0), "lch(63% 52 6 / 0.5)");
+        assert_format(Format::OklabFunction, None, "oklab(70% 0.156 0.012)");
+        assert_format(Format::OklchFunction, None, "oklch(70% 0.156 4)");
+    }
+
     #[test]
     fn format_hex() {
         assert_format(Format::Hex, None, "#eb6f92");
@@ -137,4 +234,75 @@ mod tests {
         assert_format(Format::HexNs, Some(100.0), "eb6f92ff");
         assert_format(Format::AhexNs, Some(100.0), "ffeb6f92");
     }
+
+    #[test]
+    fn format_xterm() {
+        assert_format(Format::Xterm256, None, "204");
+        assert_format(Format::Xterm256, Some(50.0), "204");
+        assert_format(Format::Xterm88, None, "65");
+        assert_format(Format::Xterm16, None, "13");
+    }
+
+    fn parse_options(format: Format) -> ParseColorOptions {
+        ParseColorOptions {
+            alpha_first: matches!(format, Format::Ahex | Format::AhexNs),
+            xterm: format.xterm_palette().unwrap_or(XtermPalette::Colors256),
+        }
+    }
+
+    /// Largest channel difference after a round trip through a format rounded to `precision` decimals
+    fn tolerance(format: Format, precision: usize) -> u8 {
+        // hex and rgb formats write the channels themselves, hsl and the css level 4 spaces are
+        // rounded. Half a degree or percent of hsl moves a channel by a few steps, while lab and
+        // oklab lightness rounded to a whole percent moves channels near 0 by up to an eighth of
+        // their range since the srgb transfer curve is steep there.
+        match (format.is_hsl(), format.is_css4(), precision) {
+            (false, false, _) => 0,
+            (true, _, 0) => 4,
+            (_, true, 0) => 32,
+            (_, true, 1) => 5,
+            _ => 1,
+        }
+    }
+
+    proptest! {
+        /// Every format parses back to the formatted color, lossy formats within `tolerance`
+        #[test]
+        fn parse_formatted(
+            r: u8,
+            g: u8,
+            b: u8,
+            alpha in proptest::option::of(0u8..=100),
+            precision in 0usize..=4,
+        ) {
+            let color = Color::from(Rgb::new(r, g, b));
+            let alpha = alpha.map(f32::from);
+
+            for format in Format::iter() {
+                let value = format.format_color(color.clone(), alpha, precision);
+                let parsed = Color::parse_with(&value, parse_options(format))
+                    .map_err(|err| TestCaseError::fail(format!("{value}: {err}")))?;
+
+                // xterm indices are quantized, so only the index has to survive
+                if format.xterm_palette().is_some() {
+                    prop_assert_eq!(format.format_color(parsed, None::<f32>, precision), value);
+                    continue;
+                }
+
+                let tolerance = tolerance(format, precision);
+                let channels = [(parsed.rgb.r, r), (parsed.rgb.g, g), (parsed.rgb.b, b)];
+                for (parsed, expected) in channels {
+                    prop_assert!(parsed.abs_diff(expected) <= tolerance, "{} parsed as {:?}", value, parsed);
+                }
+                // hex alpha is a byte, every other format writes the alpha unrounded as a fraction
+                // which only picks up float error on the way back to a percentage
+                let alpha_byte = |alpha: Option<f32>| alpha.map(|alpha| (alpha * 2.55).round() as u8);
+                match (parsed.alpha, alpha) {
+                    _ if format.is_hex() => prop_assert_eq!(alpha_byte(parsed.alpha), alpha_byte(alpha), "{}", value),
+                    (Some(parsed), Some(alpha)) => prop_assert!((parsed - alpha).abs() < 1e-4, "{}", value),
+                    (parsed, alpha) => prop_assert_eq!(parsed, alpha, "{}", value),
+                }
+            }
+        }
+    }
 }
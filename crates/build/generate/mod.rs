@@ -1,6 +1,6 @@
 use crate::{config::Config, format::Format};
 use anyhow::Result;
-use palette::Variant;
+use palette::{Role, VariantDefinition};
 use serde::Serialize;
 use std::{fs, path::Path};
 
@@ -13,16 +13,20 @@ pub struct Options {
     pub format: Format,
     pub strip_spaces: bool,
     pub force_alpha: bool,
+    /// decimal places for fractional color components
+    pub precision: usize,
+    /// composite colors with an alpha value over this role instead of outputting the alpha
+    pub flatten: Option<Role>,
 }
 
-type Template = Vec<(Variant, String)>;
+type Template<'a> = Vec<(&'a VariantDefinition, String)>;
 
-pub fn generate_template(path: &Path, config: &Config) -> Result<Template> {
+pub fn generate_template<'a>(path: &Path, config: &'a Config) -> Result<Template<'a>> {
     let template = fs::read_to_string(path)?;
 
     #[cfg(feature = "templating")]
     if config.tera {
-        return templating::generate_variants(template);
+        return templating::generate_variants(config, template);
     }
 
     Ok(replace::generate_variants(config, &template))
@@ -4,13 +4,12 @@ use crate::{
     parse::{self, Capture},
     utils::Substitutable,
 };
-use palette::Variant;
-use strum::IntoEnumIterator;
+use palette::VariantDefinition;
 
 fn replace_captures(
     captures: &[Capture],
     options: &Options,
-    variant: &Variant,
+    variant: &VariantDefinition,
     content: &str,
 ) -> String {
     let mut buffer: Vec<char> = content.to_owned().chars().collect();
@@ -22,25 +21,26 @@ fn replace_captures(
     buffer.into_iter().collect()
 }
 
-pub fn generate_variants(config: &Config, content: &str) -> Vec<(Variant, String)> {
+pub fn generate_variants<'a>(
+    config: &'a Config,
+    content: &str,
+) -> Vec<(&'a VariantDefinition, String)> {
     let captures = parse::parse_template(content, config)
         .into_iter()
         .flatten()
         .collect::<Vec<_>>();
 
-    Variant::iter()
-        .map(|v| {
-            (
-                v,
-                replace_captures(&captures, &config.generate, &v, content),
-            )
-        })
+    config
+        .variants
+        .iter()
+        .map(|v| (v, replace_captures(&captures, &config.generate, v, content)))
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use palette::{Color, ContrastTargets, Deficiency, PaletteFile, Role, Variant};
 
     #[test]
     fn generate_rgb() {
@@ -144,6 +144,213 @@ mod tests {
             .flatten()
             .collect::<Vec<_>>();
 
-        replace_captures(&captures, &config.generate, &variant, content)
+        replace_captures(&captures, &config.generate, &variant.into(), content)
+    }
+
+    #[test]
+    fn modifiers() {
+        assert_eq!(
+            generate_variant(Variant::Main, "$love.mix(base, 0)"),
+            "#eb6f92"
+        );
+        assert_eq!(
+            generate_variant(Variant::Main, "$love.mix(base, 100):hex_ns/50"),
+            "19172480"
+        );
+        assert_eq!(
+            generate_variant(Variant::Main, "$text.invert.invert()"),
+            "#e0def4"
+        );
+        assert_eq!(
+            generate_variant(Variant::Main, "$love.lighten(100) $pine.darken(100)."),
+            "#ffffff #000000."
+        );
+    }
+
+    #[test]
+    fn flatten() {
+        assert_eq!(
+            generate_variant(Variant::Main, "$love.flatten(20)"),
+            "#43293a"
+        );
+        assert_eq!(
+            generate_variant(Variant::Main, "$love.flatten(0, surface):rgb"),
+            "31, 29, 46"
+        );
+
+        let mut config = Config::default();
+        config.generate.flatten = Some(Role::Base);
+        let variants = generate_variants(&config, "$(love|love|pine)/20 $love");
+        assert_eq!(variants[0].1, "#43293a #eb6f92");
+        assert_eq!(variants[1].1, "#4b3148 #eb6f92");
+        assert_eq!(variants[2].1, "#d0d8d8 #b4637a");
+    }
+
+    #[test]
+    fn custom_roles() {
+        let mut config = Config::default();
+        config.define_role("accent=$(rose|iris|pine)").unwrap();
+        config.define_role("accent_alt=$(accent|#ff0000)").unwrap();
+        config
+            .define_role("muted_love=#eb6f92.mix(#191724, 100)")
+            .unwrap();
+
+        let variants = generate_variants(&config, "$accent:rgb/50 $accent_alt");
+        assert_eq!(variants[0].1, "235, 188, 186, 0.5 #ebbcba");
+        assert_eq!(generate_variants(&config, "$muted_love")[1].1, "#191724");
+        assert_eq!(variants[1].1, "196, 167, 231, 0.5 #c4a7e7");
+        assert_eq!(variants[2].1, "40, 105, 131, 0.5 #ff0000");
+
+        assert!(config.define_role("love=#ff0000").is_err());
+        assert!(config.define_role("brand=$love:rgb").is_err());
+    }
+
+    #[test]
+    fn reserved_role_names() {
+        let mut config = Config::default();
+        for name in [
+            "shades",
+            "aliases",
+            "ansi",
+            "accents",
+            "backgrounds",
+            "highlights",
+        ] {
+            assert!(config.define_role(&format!("{name}=#ff0000")).is_err());
+            assert!(config.define_alias(&format!("{name}=rose")).is_err());
+        }
+        assert!(config.define_role("accent=#ff0000").is_ok());
+    }
+
+    #[test]
+    fn custom_palette() {
+        let content = palette::BUILTIN
+            .replace("rose-pine-moon", "rose-pine-dusk")
+            .replace("\"moon\"", "\"dusk\"")
+            .replace("#3e8fb0", "#3e8fb1");
+        let config = Config {
+            variants: PaletteFile::from_toml(&content).unwrap().variants,
+            ..Default::default()
+        };
+
+        let variants = generate_variants(&config, "$id $pine");
+        assert_eq!(variants[1].1, "rose-pine-dusk #3e8fb1");
+        assert_eq!(variants[2].1, "rose-pine-dawn #286983");
+    }
+
+    #[test]
+    fn simulated_variants() {
+        let mut config = Config::default();
+        config.simulate(&[Deficiency::Deuteranopia, Deficiency::Achromatopsia]);
+
+        let variants = generate_variants(&config, "$id $key $(base|base|text)");
+        assert_eq!(variants.len(), 9);
+        assert_eq!(
+            variants[4].1,
+            "rose-pine-moon-deuteranopia moon-deuteranopia #1b2335"
+        );
+        assert_eq!(
+            variants[8].1,
+            "rose-pine-dawn-achromatopsia dawn-achromatopsia #575757"
+        );
+    }
+
+    #[test]
+    fn ramps() {
+        let steps = (0..5)
+            .map(|i| format!("$pine.ramp(love, 5)[{i}]"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let variant = generate_variant(Variant::Main, &steps);
+        assert!(variant.starts_with("#31748f "));
+        assert!(variant.ends_with(" #eb6f92"));
+
+        assert_eq!(
+            generate_variant(Variant::Main, "$base.ramp(text, 3, srgb)[1]"),
+            "#7d7b8c"
+        );
+    }
+
+    #[test]
+    fn shades() {
+        let variant = generate_variant(Variant::Main, "$love@400 $love@50 $love@950");
+        assert_eq!(variant, "#eb6f92 #fff2f4 #48001d");
+
+        // only known shades are read, anything else after `@` is kept as text
+        let variant = generate_variant(Variant::Main, "$love@2x $love@250.png");
+        assert_eq!(variant, "#eb6f92@2x #eb6f92@250.png");
+    }
+
+    #[test]
+    fn interpolated_variants() {
+        let mut config = Config::default();
+        config.interpolate("dusk=moon:dawn:50:oklch").unwrap();
+        config.interpolate("twilight = main : moon : 0").unwrap();
+
+        let variants = generate_variants(&config, "$id $name $key $(base|text|love)");
+        assert_eq!(variants.len(), 5);
+        assert_eq!(variants[3].1, "rose-pine-dusk Rosé Pine Dusk dusk #cf6986");
+        assert_eq!(
+            variants[4].1,
+            "rose-pine-twilight Rosé Pine Twilight twilight #191724"
+        );
+
+        assert!(config.interpolate("dusk=moon:dawn:50").is_err());
+        assert!(config.interpolate("noon=moon:sun:50").is_err());
+        assert!(config.interpolate("noon=moon:dawn:150").is_err());
+        assert!(config.interpolate("noon=moon:dawn:50:hsl").is_err());
+    }
+
+    #[test]
+    fn high_contrast_variants() {
+        let mut config = Config::default();
+        config.high_contrast(&ContrastTargets::default());
+
+        let variants = generate_variants(&config, "$id $base $muted");
+        assert_eq!(variants.len(), 6);
+        assert_eq!(variants[0].1, "rose-pine #191724 #6e6a86");
+        assert!(variants[3]
+            .1
+            .starts_with("rose-pine-high-contrast #191724 "));
+        // muted falls short of its target on base and is raised to meet it
+        let muted = variants[3].1.rsplit(' ').next().unwrap();
+        let muted = Color::parse(muted).unwrap().rgb;
+        let base = Variant::Main.get_rgb(Role::Base);
+        assert!(Variant::Main.get_rgb(Role::Muted).contrast_ratio(base) < 4.5);
+        assert!(muted.contrast_ratio(base) >= ContrastTargets::default().muted);
+    }
+
+    #[test]
+    fn aliases() {
+        let mut config = Config::default();
+        let variants = generate_variants(&config, "$error $search:rgb $search/50 $(cursor|hint)");
+        assert_eq!(
+            variants[0].1,
+            "#eb6f92 235, 188, 186, 0.2 #ebbcba80 #524f67"
+        );
+        assert_eq!(
+            variants[2].1,
+            "#b4637a 215, 130, 126, 0.2 #d7827e80 #907aa9"
+        );
+
+        config.define_alias("error=rose/80").unwrap();
+        config.define_alias("accent=iris").unwrap();
+        assert_eq!(
+            generate_variants(&config, "$error $accent")[0].1,
+            "#ebbcbacc #c4a7e7"
+        );
+
+        assert!(config.define_alias("love=rose").is_err());
+        assert!(config.define_alias("danger=lovely").is_err());
+        assert!(config.define_role("warning=#ff0000").is_err());
+    }
+
+    #[test]
+    fn ansi_colors() {
+        let content = "$ansi_black $ansi_bright_black $ansi_cyan $ansi_background";
+        let config = Config::default();
+        let variants = generate_variants(&config, content);
+        assert_eq!(variants[0].1, "#26233a #6e6a86 #ebbcba #191724");
+        assert_eq!(variants[2].1, "#f2e9de #9893a5 #d7827e #faf4ed");
     }
 }
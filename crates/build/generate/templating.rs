@@ -1,5 +1,7 @@
+use crate::config::Config;
 use anyhow::Result;
-use palette::Variant;
+use palette::{Color, RoleCategory, VariantDefinition};
+use std::collections::HashMap;
 use strum::IntoEnumIterator;
 use tera::{Context, Tera};
 
@@ -21,25 +23,211 @@ mod filters {
     }
 }
 
-fn create_context(variant: &Variant) -> Context {
+mod functions {
+    use crate::parse::ANSI_PREFIX;
+    use palette::{AnsiColor, Color, Interpolation, VariantDefinition};
+    use std::{collections::HashMap, str::FromStr};
+
+    type Args = HashMap<String, tera::Value>;
+
+    fn get_arg<T: serde::de::DeserializeOwned>(args: &Args, key: &str) -> tera::Result<T> {
+        let value = args
+            .get(key)
+            .ok_or_else(|| tera::Error::msg(format!("argument `{key}` is required")))?;
+        Ok(tera::from_value(value.clone())?)
+    }
+
+    /// Looks up a role, custom role, alias or `ansi_*` color by name
+    fn get_color(variant: &VariantDefinition, name: &str) -> tera::Result<Color> {
+        variant
+            .find_color(name)
+            .or_else(|| super::alias_color(variant, name))
+            .or_else(|| {
+                let color = AnsiColor::from_str(name.strip_prefix(ANSI_PREFIX)?).ok()?;
+                Some(Color::from(variant.get_ansi(color)))
+            })
+            .ok_or_else(|| tera::Error::msg(format!("unknown role `{name}`")))
+    }
+
+    pub fn register(tera: &mut tera::Tera, variant: &VariantDefinition) {
+        tera.register_function("flatten", flatten(variant.clone()));
+        tera.register_function("contrast", contrast(variant.clone()));
+        tera.register_function("luminance", luminance(variant.clone()));
+        tera.register_function("ramp", ramp(variant.clone()));
+    }
+
+    /// Composites `role` at `alpha` percent over `over`, which defaults to base
+    fn flatten(variant: VariantDefinition) -> impl tera::Function {
+        move |args: &Args| {
+            let color = get_color(&variant, &get_arg::<String>(args, "role")?)?;
+            let background = match args.contains_key("over") {
+                true => get_color(&variant, &get_arg::<String>(args, "over")?)?,
+                false => get_color(&variant, "base")?,
+            };
+            let alpha: f32 = get_arg(args, "alpha")?;
+
+            Ok(tera::to_value(color.flatten(&background, alpha))?)
+        }
+    }
+
+    /// Wcag and apca contrast of `fg` on `bg`, an optional `alpha` flattens `fg` over `bg` first
+    fn contrast(variant: VariantDefinition) -> impl tera::Function {
+        move |args: &Args| {
+            let mut foreground = get_color(&variant, &get_arg::<String>(args, "fg")?)?;
+            let background = get_color(&variant, &get_arg::<String>(args, "bg")?)?;
+            if args.contains_key("alpha") {
+                foreground = foreground.flatten(&background, get_arg(args, "alpha")?);
+            }
+
+            Ok(tera::to_value(foreground.contrast(&background))?)
+        }
+    }
+
+    /// Wcag relative luminance of `role`
+    fn luminance(variant: VariantDefinition) -> impl tera::Function {
+        move |args: &Args| {
+            let color = get_color(&variant, &get_arg::<String>(args, "role")?)?;
+            Ok(tera::to_value(color.relative_luminance())?)
+        }
+    }
+
+    /// `steps` evenly spaced colors from `from` to `to`, interpolated in `space` which defaults to oklab
+    fn ramp(variant: VariantDefinition) -> impl tera::Function {
+        move |args: &Args| {
+            let from = get_color(&variant, &get_arg::<String>(args, "from")?)?;
+            let to = get_color(&variant, &get_arg::<String>(args, "to")?)?;
+            let steps: usize = get_arg(args, "steps")?;
+            let space = match args.contains_key("space") {
+                true => {
+                    let space = get_arg::<String>(args, "space")?;
+                    Interpolation::from_str(&space).map_err(|_| {
+                        tera::Error::msg(format!(
+                            "unknown interpolation space `{space}`, expected srgb, oklab or oklch"
+                        ))
+                    })?
+                }
+                false => Interpolation::default(),
+            };
+
+            Ok(tera::to_value(from.ramp(&to, steps, space))?)
+        }
+    }
+}
+
+/// Color of an alias with its alpha, like `$name` in replace mode
+fn alias_color(variant: &VariantDefinition, name: &str) -> Option<Color> {
+    let (color, alpha) = variant.resolve_alias(name)?;
+    Some(Color {
+        alpha: alpha.map(f32::from),
+        ..color
+    })
+}
+
+fn create_context(variant: &VariantDefinition) -> Context {
     let mut ctx = Context::new();
     for (key, value) in variant.metadata() {
         ctx.insert(key, &value);
     }
+    let mut shades = HashMap::new();
     for (role, color) in variant.colors() {
+        shades.insert(role.clone(), color.shades());
         ctx.insert(role, &color);
     }
+    ctx.insert("shades", &shades);
+
+    let mut aliases = HashMap::new();
+    for (name, alias) in &variant.aliases {
+        ctx.insert(name, &alias_color(variant, name));
+        aliases.insert(
+            name,
+            serde_json::json!({ "role": alias.role.to_string(), "alpha": alias.alpha }),
+        );
+    }
+    ctx.insert("aliases", &aliases);
+    ctx.insert("ansi", &variant.ansi_palette());
+
+    for category in RoleCategory::iter() {
+        let roles = variant
+            .category(category)
+            .into_iter()
+            .map(|(role, color)| serde_json::json!({ "name": role.to_string(), "color": color }))
+            .collect::<Vec<_>>();
+        ctx.insert(category.plural(), &roles);
+    }
 
     ctx
 }
 
-pub fn generate_variants(template: String) -> Result<Vec<(Variant, String)>> {
+pub fn generate_variants(
+    config: &Config,
+    template: String,
+) -> Result<Vec<(&VariantDefinition, String)>> {
     let mut tera = Tera::default();
     tera.register_filter("trunc", filters::trunc);
     tera.add_raw_template("content", &template)?;
 
-    // TODO:
-    Ok(Variant::iter()
-        .map(|v| (v, tera.render("content", &create_context(&v)).unwrap()))
-        .collect())
+    config
+        .variants
+        .iter()
+        .map(|v| {
+            functions::register(&mut tera, v);
+            Ok((v, tera.render("content", &create_context(v))?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str) -> Vec<String> {
+        let config = Config {
+            tera: true,
+            ..Config::default()
+        };
+        generate_variants(&config, template.to_string())
+            .unwrap()
+            .into_iter()
+            .map(|(_, content)| content)
+            .collect()
+    }
+
+    #[test]
+    fn aliases() {
+        // `$search` is rose at 20% in replace mode as well
+        assert_eq!(
+            render("{{ search.hex }} {{ search.alpha }}")[0],
+            "ebbcba 20"
+        );
+        assert_eq!(
+            render("{{ error.hex }}{{ error.alpha | default(value=100) }}")[2],
+            "b4637a100"
+        );
+    }
+
+    #[test]
+    fn functions_resolve_aliases() {
+        let ratio = |fg: &str| {
+            render(&format!(
+                r#"{{% set c = contrast(fg="{fg}", bg="base") %}}{{{{ c.ratio }}}}"#
+            ))
+        };
+        assert_eq!(ratio("error"), ratio("love"));
+        assert_eq!(
+            render(r#"{% set c = flatten(role="selection", alpha=100) %}{{ c.hex }}"#)[0],
+            "403d52"
+        );
+        assert_eq!(
+            render(r#"{{ luminance(role="ansi_red") == luminance(role="love") }}"#)[0],
+            "true"
+        );
+        assert!(generate_variants(
+            &Config {
+                tera: true,
+                ..Config::default()
+            },
+            r#"{{ luminance(role="lovely") }}"#.into()
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,13 @@
+mod cli;
+mod config;
+mod contrast;
+mod format;
+mod generate;
+mod parse;
+mod utils;
+
+pub use cli::run;
+pub use config::{Config, TemplateOptions};
+pub use format::Format;
+pub use generate::{generate_template, Options};
+pub use parse::{Delimiter, ParseOptions};
@@ -5,15 +5,18 @@ use crate::{
     utils::{Case, Casing},
 };
 use clap::ValueEnum;
-use palette::{Color, Metadata, VariantKind};
+use palette::{
+    AnsiColor, Color, Interpolation, Metadata, Rgb, VariantDefinition, VariantKind, SHADES,
+};
 use palette::{Role, Variant};
 use serde::Serialize;
 use std::{
     fmt::{Debug, Display},
-    num::ParseIntError,
+    num::{ParseFloatError, ParseIntError},
     vec,
 };
 use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
 
 #[derive(Clone, Copy, Debug, Serialize)]
 pub struct ParseOptions {
@@ -54,14 +57,22 @@ pub enum ParseError {
     VariantNotFound,
     PrefixExpected,
     CloseDelimExpected,
+    InvalidColor,
+    InvalidExpression,
+    /// A ramp needs at least two steps and an index within them
+    InvalidStep,
     #[allow(dead_code)]
     InvalidOpacity(ParseIntError),
+    #[allow(dead_code)]
+    InvalidNumber(ParseFloatError),
 }
 
 struct Lexer {
     index: usize,
     content: Vec<char>,
     config: ParseOptions,
+    roles: Vec<String>,
+    aliases: Vec<String>,
 }
 
 impl Default for ParseOptions {
@@ -75,7 +86,7 @@ impl Default for ParseOptions {
 }
 
 impl Capture {
-    pub fn format(&self, variant: &Variant, options: &Options) -> String {
+    pub fn format(&self, variant: &VariantDefinition, options: &Options) -> String {
         match self.template {
             Template::Role(ref role, format, alpha) => {
                 let format = match format {
@@ -83,13 +94,24 @@ impl Capture {
                     None => &options.format,
                 };
 
+                let mut color = role.get_color(variant);
+                // an explicit alpha value takes precedence over the alpha of an alias
+                let alpha = alpha.or_else(|| role.select(variant).get_alpha(variant));
+                let alpha = match (options.flatten, alpha) {
+                    (Some(background), Some(alpha)) => {
+                        color = color.flatten(&variant.get_color(background), alpha.into());
+                        None
+                    }
+                    _ => alpha,
+                };
+
                 let alpha = if options.force_alpha {
                     Some(alpha.unwrap_or(100))
                 } else {
                     alpha
                 };
 
-                format.format_color(role.get_color(variant), alpha)
+                format.format_color(color, alpha, options.precision)
             }
             Template::Metadata(key, case) => {
                 let value = key.format(variant);
@@ -144,6 +166,16 @@ impl Lexer {
             index: 0,
             content: content.chars().collect(),
             config: config.parse,
+            roles: config
+                .variants
+                .first()
+                .map(|v| v.roles.keys().cloned().collect())
+                .unwrap_or_default(),
+            aliases: config
+                .variants
+                .first()
+                .map(|v| v.aliases.keys().cloned().collect())
+                .unwrap_or_default(),
         }
     }
 
@@ -203,8 +235,48 @@ impl Lexer {
             .find(|v| self.scan_ahead(&v.to_case(case)))
             .map(|v| (v, v.to_case(case).len()))
     }
+
+    /// Looks ahead for a built-in role, custom role or alias, preferring the longest match
+    /// Returns an option of the role and the matched length
+    fn scan_role(&mut self) -> Option<(RoleRef, usize)> {
+        let builtin = self
+            .scan_enum_variant::<Role>(Case::Snake)
+            .map(|(role, length)| (RoleRef::Role(role), length));
+        let custom = self
+            .roles
+            .iter()
+            .filter(|name| self.scan_ahead(name))
+            .max_by_key(|name| name.len())
+            .map(|name| (RoleRef::Custom(name.clone()), name.len()));
+        let alias = self
+            .aliases
+            .iter()
+            .filter(|name| self.scan_ahead(name))
+            .max_by_key(|name| name.len())
+            .map(|name| (RoleRef::Alias(name.clone()), name.len()));
+        let ansi = match self.scan_ahead(ANSI_PREFIX) {
+            true => {
+                self.advance_n(ANSI_PREFIX.len());
+                let color = self.scan_enum_variant::<AnsiColor>(Case::Snake);
+                self.index -= ANSI_PREFIX.len();
+                color.map(|(color, length)| (RoleRef::Ansi(color), ANSI_PREFIX.len() + length))
+            }
+            false => None,
+        };
+
+        [builtin, custom, alias, ansi]
+            .into_iter()
+            .flatten()
+            .reduce(|longest, other| match other.1 > longest.1 {
+                true => other,
+                false => longest,
+            })
+    }
 }
 
+/// Prefix of terminal colors like `$ansi_bright_black`
+pub const ANSI_PREFIX: &str = "ansi_";
+
 pub fn parse_template(content: &str, config: &Config) -> Vec<Result<Capture, ParseError>> {
     let mut lexer = Lexer::new(content, config);
     let mut captures = vec![];
@@ -233,6 +305,214 @@ where
     }
 }
 
+/// Parses the value of a custom role, either a color literal or a role capture without format or alpha
+pub fn parse_role_expression(content: &str, config: &Config) -> Result<RoleCaptures, ParseError> {
+    let mut lexer = Lexer::new(content, config);
+    let roles = if lexer.current() == Some(&'#') {
+        RoleCaptures {
+            roles: vec![parse_role(&mut lexer)?],
+            modifiers: parse_modifiers(&mut lexer)?,
+        }
+    } else {
+        match parse_capture(&mut lexer)?.template {
+            Template::Role(roles, None, None) => roles,
+            _ => return Err(ParseError::InvalidExpression),
+        }
+    };
+
+    if lexer.current().is_some() {
+        return Err(ParseError::InvalidExpression);
+    }
+
+    Ok(roles)
+}
+
+/// Parses a role name or a hex color literal
+fn parse_role(lexer: &mut Lexer) -> Result<RoleRef, ParseError> {
+    if lexer.current() == Some(&'#') {
+        let rgb = lexer
+            .content
+            .get(lexer.index + 1..lexer.index + 7)
+            .and_then(|hex| Rgb::from_hex(&hex.iter().collect::<String>()))
+            .ok_or(ParseError::InvalidColor)?;
+        lexer.advance_n(7);
+        return Ok(RoleRef::Literal(rgb));
+    }
+
+    match lexer.scan_role() {
+        Some((role, length)) => {
+            lexer.advance_n(length);
+            Ok(role)
+        }
+        None => Err(ParseError::VariantNotFound),
+    }
+}
+
+/// Parses a shade of the tonal scale like `@300`, an `@` followed by anything else, like `@2x`,
+/// is part of the surrounding text
+fn parse_shade(lexer: &mut Lexer) -> Option<Modifier> {
+    if lexer.current() != Some(&'@') {
+        return None;
+    }
+    let idx = lexer.index;
+    lexer.advance();
+
+    let mut buf = String::new();
+    while let Some(c) = lexer.current().filter(|c| c.is_ascii_digit()) {
+        buf.push(*c);
+        lexer.advance();
+    }
+
+    let shade = SHADES.into_iter().find(|shade| shade.to_string() == buf);
+    if shade.is_none() {
+        lexer.index = idx;
+    }
+    shade.map(Modifier::Shade)
+}
+
+/// Parses chained color modifiers like `.mix(base, 20).lighten(5)`
+fn parse_modifiers(lexer: &mut Lexer) -> Result<Vec<Modifier>, ParseError> {
+    let mut modifiers = vec![];
+
+    while lexer.current() == Some(&'.') {
+        let idx = lexer.index;
+        lexer.advance();
+
+        // A dot that isn't followed by a modifier is part of the surrounding text
+        let kind = match parse_enum_variant::<ModifierKind>(lexer, Case::Snake) {
+            Ok(kind) if kind.has_arguments() && lexer.current() != Some(&'(') => None,
+            Ok(kind) => Some(kind),
+            Err(_) => None,
+        };
+        let Some(kind) = kind else {
+            lexer.index = idx;
+            break;
+        };
+
+        modifiers.push(parse_modifier(lexer, kind)?);
+    }
+
+    Ok(modifiers)
+}
+
+fn parse_modifier(lexer: &mut Lexer, kind: ModifierKind) -> Result<Modifier, ParseError> {
+    let has_parenthesis = lexer.current() == Some(&'(');
+    if has_parenthesis {
+        lexer.advance();
+        lexer.skip_whitespace();
+    }
+
+    let mut modifier = match kind {
+        ModifierKind::Lighten => Modifier::Lighten(parse_number(lexer)?),
+        ModifierKind::Darken => Modifier::Darken(parse_number(lexer)?),
+        ModifierKind::Saturate => Modifier::Saturate(parse_number(lexer)?),
+        ModifierKind::Desaturate => Modifier::Desaturate(parse_number(lexer)?),
+        ModifierKind::HueRotate => Modifier::HueRotate(parse_number(lexer)?),
+        ModifierKind::Mix => {
+            let role = parse_role(lexer)?;
+            lexer.skip_whitespace();
+
+            let ratio = if lexer.current() == Some(&',') {
+                lexer.advance();
+                lexer.skip_whitespace();
+                parse_number(lexer)?
+            } else {
+                50.0
+            };
+
+            Modifier::Mix(role, ratio)
+        }
+        ModifierKind::Invert => Modifier::Invert,
+        ModifierKind::Grayscale => Modifier::Grayscale,
+        ModifierKind::Flatten => {
+            let alpha = parse_number(lexer)?;
+            lexer.skip_whitespace();
+
+            let background = if lexer.current() == Some(&',') {
+                lexer.advance();
+                lexer.skip_whitespace();
+                parse_role(lexer)?
+            } else {
+                Role::Base.into()
+            };
+
+            Modifier::Flatten(alpha, background)
+        }
+        ModifierKind::Ramp => {
+            let role = parse_role(lexer)?;
+            lexer.skip_whitespace();
+            if lexer.current() != Some(&',') {
+                return Err(ParseError::InvalidStep);
+            }
+            lexer.advance();
+            lexer.skip_whitespace();
+            let steps = parse_number(lexer)?;
+            lexer.skip_whitespace();
+
+            let space = if lexer.current() == Some(&',') {
+                lexer.advance();
+                lexer.skip_whitespace();
+                parse_enum_variant::<Interpolation>(lexer, Case::Snake)?
+            } else {
+                Interpolation::default()
+            };
+
+            // the index of the step follows the arguments, e.g. `.ramp(love, 5)[2]`
+            if steps.fract() != 0.0 {
+                return Err(ParseError::InvalidStep);
+            }
+
+            Modifier::Ramp(role, steps as usize, space, 0)
+        }
+    };
+
+    if has_parenthesis {
+        lexer.skip_whitespace();
+        if lexer.current() != Some(&')') {
+            return Err(ParseError::CloseDelimExpected);
+        }
+        lexer.advance();
+    }
+
+    if let Modifier::Ramp(_, steps, _, ref mut index) = modifier {
+        if lexer.current() != Some(&'[') {
+            return Err(ParseError::InvalidStep);
+        }
+        lexer.advance();
+        let step = parse_number(lexer)?;
+        if step.fract() != 0.0 || step < 0.0 {
+            return Err(ParseError::InvalidStep);
+        }
+        *index = step as usize;
+        if lexer.current() != Some(&']') {
+            return Err(ParseError::CloseDelimExpected);
+        }
+        lexer.advance();
+
+        if steps < 2 || *index >= steps {
+            return Err(ParseError::InvalidStep);
+        }
+    }
+
+    Ok(modifier)
+}
+
+fn parse_number(lexer: &mut Lexer) -> Result<f32, ParseError> {
+    let mut buf = String::new();
+
+    while let Some(c) = lexer.current() {
+        match c.is_ascii_digit() || *c == '.' || (*c == '-' && buf.is_empty()) {
+            true => {
+                buf.push(*c);
+                lexer.advance();
+            }
+            false => break,
+        }
+    }
+
+    buf.parse().map_err(ParseError::InvalidNumber)
+}
+
 fn parse_capture(lexer: &mut Lexer) -> Result<Capture, ParseError> {
     let mut roles = RoleCaptures::new();
 
@@ -242,7 +522,13 @@ fn parse_capture(lexer: &mut Lexer) -> Result<Capture, ParseError> {
     }
     lexer.advance();
 
-    if let Ok(key) = parse_enum_variant::<Metadata>(lexer, Case::Snake) {
+    // Custom roles can start with a metadata key, in which case the longest match wins
+    let role_length = lexer.scan_role().map_or(0, |(_, length)| length);
+    if let Some((key, length)) = lexer
+        .scan_enum_variant::<Metadata>(Case::Snake)
+        .filter(|(_, length)| *length >= role_length)
+    {
+        lexer.advance_n(length);
         let idx = lexer.index;
         let format = if lexer.current() == Some(&':') {
             lexer.advance();
@@ -268,19 +554,19 @@ fn parse_capture(lexer: &mut Lexer) -> Result<Capture, ParseError> {
     if lexer.current() == Some(&lexer.config.delimiter.open()) {
         lexer.advance();
         lexer.skip_whitespace();
-        roles.push(parse_enum_variant(lexer, Case::Snake)?);
+        roles.push(parse_role(lexer)?);
         lexer.skip_whitespace();
 
         if lexer.current() == Some(&lexer.config.seperator) {
             lexer.advance();
             lexer.skip_whitespace();
-            roles.push(parse_enum_variant(lexer, Case::Snake)?);
+            roles.push(parse_role(lexer)?);
             lexer.skip_whitespace();
 
             if lexer.current() == Some(&lexer.config.seperator) {
                 lexer.advance();
                 lexer.skip_whitespace();
-                roles.push(parse_enum_variant(lexer, Case::Snake)?);
+                roles.push(parse_role(lexer)?);
             }
             lexer.skip_whitespace();
         }
@@ -292,8 +578,13 @@ fn parse_capture(lexer: &mut Lexer) -> Result<Capture, ParseError> {
     }
     // Role name without group
     else {
-        roles.push(parse_enum_variant(lexer, Case::Snake)?);
+        roles.push(parse_role(lexer)?);
+    }
+
+    if let Some(shade) = parse_shade(lexer) {
+        roles.modifiers.push(shade);
     }
+    roles.modifiers.extend(parse_modifiers(lexer)?);
 
     let format = if lexer.current() == Some(&':') {
         lexer.advance();
@@ -337,34 +628,163 @@ fn parse_capture(lexer: &mut Lexer) -> Result<Capture, ParseError> {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct RoleCaptures(Vec<Role>);
+pub enum RoleRef {
+    Role(Role),
+    Custom(String),
+    Alias(String),
+    Ansi(AnsiColor),
+    Literal(Rgb),
+}
+
+impl From<Role> for RoleRef {
+    fn from(value: Role) -> Self {
+        Self::Role(value)
+    }
+}
+
+impl RoleRef {
+    fn get_color(&self, variant: &VariantDefinition) -> Color {
+        match self {
+            Self::Role(role) => variant.get_color(*role),
+            Self::Custom(name) => variant
+                .get_custom_color(name)
+                .expect("custom roles are defined for every variant"),
+            Self::Alias(name) => {
+                variant
+                    .resolve_alias(name)
+                    .expect("aliases are defined for every variant")
+                    .0
+            }
+            Self::Ansi(color) => Color::from(variant.get_ansi(*color)),
+            Self::Literal(rgb) => Color::from(*rgb),
+        }
+    }
+
+    /// Alpha of an alias that refers to a role at an alpha value
+    fn get_alpha(&self, variant: &VariantDefinition) -> Option<u16> {
+        match self {
+            Self::Alias(name) => variant.resolve_alias(name).and_then(|(_, alpha)| alpha),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ModifierKind {
+    Lighten,
+    Darken,
+    Saturate,
+    Desaturate,
+    Mix,
+    HueRotate,
+    Invert,
+    Grayscale,
+    Flatten,
+    Ramp,
+}
+
+impl ModifierKind {
+    fn has_arguments(&self) -> bool {
+        !matches!(self, Self::Invert | Self::Grayscale)
+    }
+}
+
+/// Adjustment applied to the color of a role, amounts are percentages
+#[derive(Debug, Clone, PartialEq)]
+pub enum Modifier {
+    Lighten(f32),
+    Darken(f32),
+    Saturate(f32),
+    Desaturate(f32),
+    Mix(RoleRef, f32),
+    HueRotate(f32),
+    Invert,
+    Grayscale,
+    Flatten(f32, RoleRef),
+    /// Shade of the tonal scale of the color
+    Shade(u16),
+    /// Step at an index of a ramp towards a role with a number of steps
+    Ramp(RoleRef, usize, Interpolation, usize),
+}
+
+impl Modifier {
+    fn apply(&self, color: &Color, variant: &VariantDefinition) -> Color {
+        match self {
+            Self::Lighten(amount) => color.lighten(*amount),
+            Self::Darken(amount) => color.darken(*amount),
+            Self::Saturate(amount) => color.saturate(*amount),
+            Self::Desaturate(amount) => color.desaturate(*amount),
+            Self::Mix(role, ratio) => color.mix(&role.get_color(variant), *ratio),
+            Self::HueRotate(degrees) => color.hue_rotate(*degrees),
+            Self::Invert => color.invert(),
+            Self::Grayscale => color.grayscale(),
+            Self::Flatten(alpha, background) => {
+                color.flatten(&background.get_color(variant), *alpha)
+            }
+            Self::Shade(step) => color
+                .shade(*step)
+                .expect("shades are validated while parsing"),
+            Self::Ramp(role, steps, space, index) => {
+                let t = *index as f32 / (*steps - 1) as f32;
+                color.interpolate(&role.get_color(variant), t, *space)
+            }
+        }
+    }
+}
+
+/// Roles to pick from depending on the variant, followed by modifiers applied to the picked color
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleCaptures {
+    roles: Vec<RoleRef>,
+    modifiers: Vec<Modifier>,
+}
+
+impl<T: Into<RoleRef>> From<Vec<T>> for RoleCaptures {
+    fn from(value: Vec<T>) -> Self {
+        Self {
+            roles: value.into_iter().map(Into::into).collect(),
+            modifiers: vec![],
+        }
+    }
+}
 
 impl RoleCaptures {
     fn new() -> Self {
-        Self(Vec::with_capacity(3))
+        Self {
+            roles: Vec::with_capacity(3),
+            modifiers: vec![],
+        }
     }
 
-    fn push(&mut self, val: Role) {
-        if self.0.len() < 3 {
-            self.0.push(val)
+    fn push(&mut self, val: RoleRef) {
+        if self.roles.len() < 3 {
+            self.roles.push(val)
         }
     }
 
-    fn get_color(&self, variant: &Variant) -> Color {
-        match self.0.as_slice() {
+    /// Role of the group that applies to a variant
+    fn select(&self, variant: &VariantDefinition) -> &RoleRef {
+        match self.roles.as_slice() {
             [role] => role,
-            [dark, light] => match variant.kind() {
+            [dark, light] => match variant.kind {
                 VariantKind::Light => light,
                 VariantKind::Dark => dark,
             },
-            [main, moon, dawn] => match variant {
+            [main, moon, dawn] => match variant.slot() {
                 Variant::Main => main,
                 Variant::Moon => moon,
                 Variant::Dawn => dawn,
             },
             _ => unreachable!(),
         }
-        .get_color(variant)
+    }
+
+    pub fn get_color(&self, variant: &VariantDefinition) -> Color {
+        self.modifiers.iter().fold(
+            self.select(variant).get_color(variant),
+            |color, modifier| modifier.apply(&color, variant),
+        )
     }
 }
 
@@ -94,11 +94,19 @@ fn format() {
     assert_role("$base:ahex", vec![Role::Base], Some(Format::Ahex), None);
     assert_capture(
         "$base:hex_ns",
-        Template::Role(RoleCaptures(vec![Role::Base]), Some(Format::HexNs), None),
+        Template::Role(
+            RoleCaptures::from(vec![Role::Base]),
+            Some(Format::HexNs),
+            None,
+        ),
     );
     assert_capture(
         "$base:ahex_ns",
-        Template::Role(RoleCaptures(vec![Role::Base]), Some(Format::AhexNs), None),
+        Template::Role(
+            RoleCaptures::from(vec![Role::Base]),
+            Some(Format::AhexNs),
+            None,
+        ),
     );
 }
 
@@ -135,8 +143,239 @@ fn metadata() {
     assert_metadata("$name:", Metadata::Name, None, 0, 4);
 }
 
+#[test]
+fn custom_roles() {
+    let mut config = Config::default();
+    config.define_role("love_bg=$surface").unwrap();
+    config.define_role("kind_accent=#ff0000").unwrap();
+
+    let parse = |content: &str| {
+        let mut lexer = Lexer::new(content, &config);
+        parse::parse_capture(&mut lexer).unwrap().template
+    };
+
+    assert_eq!(
+        parse("$love_bg:rgb/50"),
+        Template::Role(
+            RoleCaptures::from(vec![RoleRef::Custom("love_bg".into())]),
+            Some(Format::Rgb),
+            Some(50)
+        )
+    );
+    assert_eq!(
+        parse("$(love|kind_accent)"),
+        Template::Role(
+            RoleCaptures::from(vec![
+                Role::Love.into(),
+                RoleRef::Custom("kind_accent".into())
+            ]),
+            None,
+            None
+        )
+    );
+    assert_eq!(
+        parse("$kind:upper"),
+        Template::Metadata(Metadata::Kind, Some(Case::Upper))
+    );
+}
+
+#[test]
+fn color_literals() {
+    assert_capture(
+        "$(#ff0000|love)",
+        Template::Role(
+            RoleCaptures::from(vec![
+                RoleRef::Literal(Rgb::new(255, 0, 0)),
+                Role::Love.into(),
+            ]),
+            None,
+            None,
+        ),
+    );
+}
+
+#[test]
+fn modifiers() {
+    assert_capture(
+        "$love.mix(base, 20):rgb/50",
+        Template::Role(
+            RoleCaptures {
+                roles: vec![Role::Love.into()],
+                modifiers: vec![Modifier::Mix(Role::Base.into(), 20.0)],
+            },
+            Some(Format::Rgb),
+            Some(50),
+        ),
+    );
+    assert_capture(
+        "$(rose|pine).lighten(-2.5).invert.hue_rotate( 90 )",
+        Template::Role(
+            RoleCaptures {
+                roles: vec![Role::Rose.into(), Role::Pine.into()],
+                modifiers: vec![
+                    Modifier::Lighten(-2.5),
+                    Modifier::Invert,
+                    Modifier::HueRotate(90.0),
+                ],
+            },
+            None,
+            None,
+        ),
+    );
+    assert_capture(
+        "$love.flatten(20, surface)",
+        Template::Role(
+            RoleCaptures {
+                roles: vec![Role::Love.into()],
+                modifiers: vec![Modifier::Flatten(20.0, Role::Surface.into())],
+            },
+            None,
+            None,
+        ),
+    );
+    // dots that aren't followed by a modifier are left alone
+    assert_role_with_pos("$love.", vec![Role::Love], None, None, 0, 4);
+    assert_role_with_pos("$love.lighten", vec![Role::Love], None, None, 0, 4);
+}
+
+#[test]
+fn ramps() {
+    assert_capture(
+        "$pine.ramp(love, 5)[2]:rgb",
+        Template::Role(
+            RoleCaptures {
+                roles: vec![Role::Pine.into()],
+                modifiers: vec![Modifier::Ramp(
+                    Role::Love.into(),
+                    5,
+                    Interpolation::Oklab,
+                    2,
+                )],
+            },
+            Some(Format::Rgb),
+            None,
+        ),
+    );
+    assert_capture(
+        "$(base|base|surface).ramp(#ff0000, 3, oklch)[0]/50",
+        Template::Role(
+            RoleCaptures {
+                roles: vec![Role::Base.into(), Role::Base.into(), Role::Surface.into()],
+                modifiers: vec![Modifier::Ramp(
+                    RoleRef::Literal(Rgb::new(255, 0, 0)),
+                    3,
+                    Interpolation::Oklch,
+                    0,
+                )],
+            },
+            None,
+            Some(50),
+        ),
+    );
+
+    let config = Config::default();
+    for invalid in [
+        "$pine.ramp(love, 5)",
+        "$pine.ramp(love, 5)[5]",
+        "$pine.ramp(love, 1)[0]",
+        "$pine.ramp(love, 2.5)[1]",
+        "$pine.ramp(love)[1]",
+    ] {
+        let mut lexer = Lexer::new(invalid, &config);
+        assert!(parse::parse_capture(&mut lexer).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn shades() {
+    assert_capture(
+        "$love@300.darken(5):hex",
+        Template::Role(
+            RoleCaptures {
+                roles: vec![Role::Love.into()],
+                modifiers: vec![Modifier::Shade(300), Modifier::Darken(5.0)],
+            },
+            Some(Format::Hex),
+            None,
+        ),
+    );
+    // an `@` that isn't followed by a shade is left alone
+    assert_role_with_pos("$love@home", vec![Role::Love], None, None, 0, 4);
+    assert_role_with_pos("$love@2x", vec![Role::Love], None, None, 0, 4);
+    assert_role_with_pos("$love@250", vec![Role::Love], None, None, 0, 4);
+}
+
+#[test]
+fn aliases() {
+    assert_capture(
+        "$(error|selection):rgb/50",
+        Template::Role(
+            RoleCaptures::from(vec![
+                RoleRef::Alias("error".into()),
+                RoleRef::Alias("selection".into()),
+            ]),
+            Some(Format::Rgb),
+            Some(50),
+        ),
+    );
+
+    // the longest match wins, `$info` is the alias rather than a custom `in` role followed by `fo`
+    let mut config = Config::default();
+    config.define_role("in=$foam").unwrap();
+    let parse = |content: &str| {
+        let mut lexer = Lexer::new(content, &config);
+        parse::parse_capture(&mut lexer).unwrap().template
+    };
+    assert_eq!(
+        parse("$info"),
+        Template::Role(
+            RoleCaptures::from(vec![RoleRef::Alias("info".into())]),
+            None,
+            None
+        )
+    );
+    assert_eq!(
+        parse("$in"),
+        Template::Role(
+            RoleCaptures::from(vec![RoleRef::Custom("in".into())]),
+            None,
+            None
+        )
+    );
+}
+
+#[test]
+fn ansi_colors() {
+    assert_capture(
+        "$ansi_bright_black:rgb",
+        Template::Role(
+            RoleCaptures::from(vec![RoleRef::Ansi(AnsiColor::BrightBlack)]),
+            Some(Format::Rgb),
+            None,
+        ),
+    );
+    assert_capture(
+        "$(ansi_black|ansi_selection)",
+        Template::Role(
+            RoleCaptures::from(vec![
+                RoleRef::Ansi(AnsiColor::Black),
+                RoleRef::Ansi(AnsiColor::Selection),
+            ]),
+            None,
+            None,
+        ),
+    );
+
+    let config = Config::default();
+    let mut lexer = Lexer::new("$ansi_orange", &config);
+    assert!(parse::parse_capture(&mut lexer).is_err());
+}
+
 fn assert_role(content: &str, roles: Vec<Role>, format: Option<Format>, alpha: Option<u16>) {
-    assert_capture(content, Template::Role(RoleCaptures(roles), format, alpha));
+    assert_capture(
+        content,
+        Template::Role(RoleCaptures::from(roles), format, alpha),
+    );
 }
 
 fn assert_role_with_pos(
@@ -152,7 +391,7 @@ fn assert_role_with_pos(
     let correct = Capture {
         start,
         end,
-        template: Template::Role(RoleCaptures(roles), format, alpha),
+        template: Template::Role(RoleCaptures::from(roles), format, alpha),
     };
 
     match parse::parse_capture(&mut lexer) {
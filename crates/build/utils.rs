@@ -56,3 +56,46 @@ impl<T: ?Sized + Display> Casing for T {
         }
     }
 }
+
+/// Renders rows as columns aligned to the widest cell, or as a markdown table
+pub(crate) fn table<const N: usize>(
+    header: [&str; N],
+    rows: &[[String; N]],
+    markdown: bool,
+) -> String {
+    let header = header.map(String::from);
+    let widths = rows.iter().fold(
+        header.clone().map(|col| col.chars().count()),
+        |mut widths, row| {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+            widths
+        },
+    );
+
+    let mut out = String::new();
+    let mut write_row = |row: &[String; N]| {
+        let cells = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>();
+        let line = match markdown {
+            true => format!("| {} |", cells.join(" | ")),
+            false => cells.join("  ").trim_end().to_string(),
+        };
+        out.push_str(&line);
+        out.push('\n');
+    };
+
+    write_row(&header);
+    if markdown {
+        write_row(&widths.map(|width| "-".repeat(width)));
+    }
+    for row in rows {
+        write_row(row);
+    }
+
+    out
+}
@@ -0,0 +1,320 @@
+//! C API for the built-in variants, declared in `rose_pine.h`.
+//!
+//! Variants and roles are passed as indices in palette order, `rose_pine_variant_count` and
+//! `rose_pine_role_count` bound them and the lookup functions map names to indices.
+//! Strings are written into caller supplied buffers like `snprintf`.
+
+use palette::{format_into, Role, Variant};
+use std::{
+    ffi::{c_char, CStr},
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
+
+/// Hex color with a leading `#`, e.g. `#ebbcba`
+pub const ROSE_PINE_FORMAT_HEX: u32 = 0;
+/// Hex digits without a leading `#`, e.g. `ebbcba`
+pub const ROSE_PINE_FORMAT_HEX_NS: u32 = 1;
+/// Css rgb function, e.g. `rgb(235, 188, 186)`
+pub const ROSE_PINE_FORMAT_RGB: u32 = 2;
+/// Css hsl function, e.g. `hsl(2, 55%, 83%)`
+pub const ROSE_PINE_FORMAT_HSL: u32 = 3;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RosePineRgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// Hue in degrees, saturation and lightness in percentages
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RosePineHsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+fn variant(index: usize) -> Option<Variant> {
+    Variant::iter().nth(index)
+}
+
+fn role(index: usize) -> Option<Role> {
+    Role::iter().nth(index)
+}
+
+/// Copies `value` into `buf` with a nul terminator, truncated at a char boundary when it doesn't fit.
+/// Returns the length of `value` without the terminator.
+unsafe fn write_str(value: &str, buf: *mut c_char, len: usize) -> isize {
+    if !buf.is_null() && len > 0 {
+        let mut written = value.len().min(len - 1);
+        while !value.is_char_boundary(written) {
+            written -= 1;
+        }
+        std::ptr::copy_nonoverlapping(value.as_ptr().cast(), buf, written);
+        *buf.add(written) = 0;
+    }
+    value.len() as isize
+}
+
+/// Position of a nul terminated name in `names`, -1 when it is null, not utf-8 or unknown
+unsafe fn lookup<T: PartialEq>(
+    name: *const c_char,
+    mut names: impl Iterator<Item = T>,
+    find: impl Fn(&str) -> Option<T>,
+) -> isize {
+    if name.is_null() {
+        return -1;
+    }
+    CStr::from_ptr(name)
+        .to_str()
+        .ok()
+        .and_then(find)
+        .and_then(|found| names.position(|item| item == found))
+        .map_or(-1, |index| index as isize)
+}
+
+/// Number of built-in variants
+#[no_mangle]
+pub extern "C" fn rose_pine_variant_count() -> usize {
+    Variant::iter().count()
+}
+
+/// Number of roles in every variant
+#[no_mangle]
+pub extern "C" fn rose_pine_role_count() -> usize {
+    Role::iter().count()
+}
+
+/// Writes the key of a variant, e.g. `moon`.
+/// Returns the length of the key, or -1 when the variant is out of range.
+///
+/// # Safety
+/// `buf` is null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rose_pine_variant_key(
+    variant: usize,
+    buf: *mut c_char,
+    len: usize,
+) -> isize {
+    self::variant(variant).map_or(-1, |variant| write_str(variant.key(), buf, len))
+}
+
+/// Writes the display name of a variant, e.g. `Rosé Pine Moon`.
+/// Returns the length of the name in bytes, or -1 when the variant is out of range.
+///
+/// # Safety
+/// `buf` is null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rose_pine_variant_name(
+    variant: usize,
+    buf: *mut c_char,
+    len: usize,
+) -> isize {
+    self::variant(variant).map_or(-1, |variant| write_str(variant.name(), buf, len))
+}
+
+/// Writes the name of a role, e.g. `highlight_med`.
+/// Returns the length of the name, or -1 when the role is out of range.
+///
+/// # Safety
+/// `buf` is null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rose_pine_role_name(role: usize, buf: *mut c_char, len: usize) -> isize {
+    let mut name = [0; 32];
+    match self::role(role).map(|role| format_into(&mut name, format_args!("{role}"))) {
+        Some(Ok(name)) => write_str(name, buf, len),
+        _ => -1,
+    }
+}
+
+/// Index of a variant by its id, key or name ignoring case, -1 when unknown
+///
+/// # Safety
+/// `name` is null or a nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rose_pine_variant_lookup(name: *const c_char) -> isize {
+    lookup(name, Variant::iter(), Variant::lookup)
+}
+
+/// Index of a role by its name, e.g. `highlight_med`, -1 when unknown
+///
+/// # Safety
+/// `name` is null or a nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rose_pine_role_lookup(name: *const c_char) -> isize {
+    lookup(name, Role::iter(), |name| Role::from_str(name).ok())
+}
+
+/// Stores the rgb color of a role in `out`, returns false when the variant or role is out of range
+///
+/// # Safety
+/// `out` is null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rose_pine_rgb(variant: usize, role: usize, out: *mut RosePineRgb) -> bool {
+    let (Some(variant), Some(role), false) =
+        (self::variant(variant), self::role(role), out.is_null())
+    else {
+        return false;
+    };
+    let rgb = variant.get_rgb(role);
+    *out = RosePineRgb {
+        r: rgb.r,
+        g: rgb.g,
+        b: rgb.b,
+    };
+    true
+}
+
+/// Stores the hsl color of a role in `out`, returns false when the variant or role is out of range
+///
+/// # Safety
+/// `out` is null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rose_pine_hsl(variant: usize, role: usize, out: *mut RosePineHsl) -> bool {
+    let (Some(variant), Some(role), false) =
+        (self::variant(variant), self::role(role), out.is_null())
+    else {
+        return false;
+    };
+    let hsl = variant.get_hsl(role);
+    *out = RosePineHsl {
+        h: hsl.h,
+        s: hsl.s,
+        l: hsl.l,
+    };
+    true
+}
+
+/// Writes the color of a role in one of the `ROSE_PINE_FORMAT_*` formats.
+/// Returns the length of the formatted color, or -1 when the variant, role or format is unknown.
+///
+/// # Safety
+/// `buf` is null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rose_pine_format(
+    variant: usize,
+    role: usize,
+    format: u32,
+    buf: *mut c_char,
+    len: usize,
+) -> isize {
+    let (Some(variant), Some(role)) = (self::variant(variant), self::role(role)) else {
+        return -1;
+    };
+    let rgb = variant.get_rgb(role);
+
+    let mut formatted = [0; 32];
+    let formatted = match format {
+        ROSE_PINE_FORMAT_HEX => format_into(&mut formatted, format_args!("{rgb}")),
+        ROSE_PINE_FORMAT_HEX_NS => format_into(&mut formatted, format_args!("{rgb:x}")),
+        ROSE_PINE_FORMAT_RGB => format_into(
+            &mut formatted,
+            format_args!("rgb({}, {}, {})", rgb.r, rgb.g, rgb.b),
+        ),
+        ROSE_PINE_FORMAT_HSL => format_into(&mut formatted, format_args!("{}", rgb.to_hsl())),
+        _ => return -1,
+    };
+    formatted.map_or(-1, |formatted| write_str(formatted, buf, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    /// Generated by cbindgen, checked in for C and Zig consumers
+    const HEADER: &str = include_str!("rose_pine.h");
+
+    #[test]
+    #[ignore = "runs cbindgen, check with `cargo test -p palette-ffi -- --ignored`"]
+    fn checked_in_header_is_current() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let config = cbindgen::Config::from_file(format!("{dir}/cbindgen.toml")).unwrap();
+        let mut header = Vec::new();
+        cbindgen::generate_with_config(dir, config)
+            .unwrap()
+            .write(&mut header);
+        assert_eq!(
+            String::from_utf8(header).unwrap(),
+            HEADER,
+            "rose_pine.h is outdated, regenerate it with `cbindgen crates/ffi -o crates/ffi/rose_pine.h`"
+        );
+    }
+
+    fn read(write: impl Fn(*mut c_char, usize) -> isize) -> (isize, String) {
+        let mut buf = [0 as c_char; 16];
+        let written = write(buf.as_mut_ptr(), buf.len());
+        let value = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        (written, value.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn enumerate() {
+        assert_eq!(rose_pine_variant_count(), 3);
+        assert_eq!(rose_pine_role_count(), 15);
+        assert_eq!(
+            read(|buf, len| unsafe { rose_pine_variant_key(1, buf, len) }),
+            (4, "moon".into())
+        );
+        assert_eq!(
+            read(|buf, len| unsafe { rose_pine_role_name(13, buf, len) }),
+            (13, "highlight_med".into())
+        );
+        assert_eq!(unsafe { rose_pine_variant_key(3, ptr::null_mut(), 0) }, -1);
+
+        // `é` is two bytes, the truncated name stops before it instead of splitting it
+        let mut short = [0 as c_char; 5];
+        assert_eq!(
+            unsafe { rose_pine_variant_name(0, short.as_mut_ptr(), short.len()) },
+            10
+        );
+        assert_eq!(
+            unsafe { CStr::from_ptr(short.as_ptr()) }.to_str(),
+            Ok("Ros")
+        );
+        assert_eq!(
+            unsafe { rose_pine_variant_lookup(c"Rosé Pine Dawn".as_ptr()) },
+            2
+        );
+        assert_eq!(unsafe { rose_pine_role_lookup(c"iris".as_ptr()) }, 11);
+        assert_eq!(unsafe { rose_pine_role_lookup(c"purple".as_ptr()) }, -1);
+        assert_eq!(unsafe { rose_pine_role_lookup(ptr::null()) }, -1);
+    }
+
+    #[test]
+    fn colors() {
+        let mut rgb = RosePineRgb::default();
+        assert!(unsafe { rose_pine_rgb(0, 8, &mut rgb) });
+        assert_eq!(
+            rgb,
+            RosePineRgb {
+                r: 235,
+                g: 188,
+                b: 186
+            }
+        );
+        assert!(!unsafe { rose_pine_rgb(0, 15, &mut rgb) });
+
+        let mut hsl = RosePineHsl::default();
+        assert!(unsafe { rose_pine_hsl(0, 8, &mut hsl) });
+        assert_eq!(hsl.h.round(), 2.0);
+    }
+
+    #[test]
+    fn format() {
+        let format =
+            |format| read(move |buf, len| unsafe { rose_pine_format(0, 8, format, buf, len) });
+        assert_eq!(format(ROSE_PINE_FORMAT_HEX), (7, "#ebbcba".into()));
+        assert_eq!(format(ROSE_PINE_FORMAT_HEX_NS), (6, "ebbcba".into()));
+        assert_eq!(format(ROSE_PINE_FORMAT_RGB), (18, "rgb(235, 188, 1".into()));
+        assert_eq!(format(ROSE_PINE_FORMAT_HSL), (16, "hsl(2, 55%, 83%".into()));
+        assert_eq!(format(4).0, -1);
+        assert_eq!(
+            unsafe { rose_pine_format(0, 8, ROSE_PINE_FORMAT_RGB, ptr::null_mut(), 0) },
+            18
+        );
+    }
+}
@@ -0,0 +1,125 @@
+#ifndef ROSE_PINE_H
+#define ROSE_PINE_H
+
+/* Generated by cbindgen from crates/ffi/lib.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Hex color with a leading `#`, e.g. `#ebbcba`
+ */
+#define ROSE_PINE_FORMAT_HEX 0
+
+/**
+ * Hex digits without a leading `#`, e.g. `ebbcba`
+ */
+#define ROSE_PINE_FORMAT_HEX_NS 1
+
+/**
+ * Css rgb function, e.g. `rgb(235, 188, 186)`
+ */
+#define ROSE_PINE_FORMAT_RGB 2
+
+/**
+ * Css hsl function, e.g. `hsl(2, 55%, 83%)`
+ */
+#define ROSE_PINE_FORMAT_HSL 3
+
+typedef struct RosePineRgb {
+  uint8_t r;
+  uint8_t g;
+  uint8_t b;
+} RosePineRgb;
+
+/**
+ * Hue in degrees, saturation and lightness in percentages
+ */
+typedef struct RosePineHsl {
+  float h;
+  float s;
+  float l;
+} RosePineHsl;
+
+/**
+ * Number of built-in variants
+ */
+size_t rose_pine_variant_count(void);
+
+/**
+ * Number of roles in every variant
+ */
+size_t rose_pine_role_count(void);
+
+/**
+ * Writes the key of a variant, e.g. `moon`.
+ * Returns the length of the key, or -1 when the variant is out of range.
+ *
+ * # Safety
+ * `buf` is null or valid for writes of `len` bytes.
+ */
+ptrdiff_t rose_pine_variant_key(size_t variant, char *buf, size_t len);
+
+/**
+ * Writes the display name of a variant, e.g. `Rosé Pine Moon`.
+ * Returns the length of the name in bytes, or -1 when the variant is out of range.
+ *
+ * # Safety
+ * `buf` is null or valid for writes of `len` bytes.
+ */
+ptrdiff_t rose_pine_variant_name(size_t variant, char *buf, size_t len);
+
+/**
+ * Writes the name of a role, e.g. `highlight_med`.
+ * Returns the length of the name, or -1 when the role is out of range.
+ *
+ * # Safety
+ * `buf` is null or valid for writes of `len` bytes.
+ */
+ptrdiff_t rose_pine_role_name(size_t role, char *buf, size_t len);
+
+/**
+ * Index of a variant by its id, key or name ignoring case, -1 when unknown
+ *
+ * # Safety
+ * `name` is null or a nul terminated string.
+ */
+ptrdiff_t rose_pine_variant_lookup(const char *name);
+
+/**
+ * Index of a role by its name, e.g. `highlight_med`, -1 when unknown
+ *
+ * # Safety
+ * `name` is null or a nul terminated string.
+ */
+ptrdiff_t rose_pine_role_lookup(const char *name);
+
+/**
+ * Stores the rgb color of a role in `out`, returns false when the variant or role is out of range
+ *
+ * # Safety
+ * `out` is null or valid for writes.
+ */
+bool rose_pine_rgb(size_t variant, size_t role, struct RosePineRgb *out);
+
+/**
+ * Stores the hsl color of a role in `out`, returns false when the variant or role is out of range
+ *
+ * # Safety
+ * `out` is null or valid for writes.
+ */
+bool rose_pine_hsl(size_t variant, size_t role, struct RosePineHsl *out);
+
+/**
+ * Writes the color of a role in one of the `ROSE_PINE_FORMAT_*` formats.
+ * Returns the length of the formatted color, or -1 when the variant, role or format is unknown.
+ *
+ * # Safety
+ * `buf` is null or valid for writes of `len` bytes.
+ */
+ptrdiff_t rose_pine_format(size_t variant, size_t role, uint32_t format, char *buf, size_t len);
+
+#endif  /* ROSE_PINE_H */
@@ -0,0 +1,53 @@
+use palette::{Role, Variant};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use std::str::FromStr;
+use strum::{IntoEnumIterator, VariantNames};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, Token,
+};
+
+/// A role of a variant written as `variant.role`
+struct VariantRole {
+    variant: Ident,
+    role: Ident,
+}
+
+impl Parse for VariantRole {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let variant = input.parse()?;
+        input.parse::<Token![.]>()?;
+        let role = input.parse()?;
+        Ok(Self { variant, role })
+    }
+}
+
+/// Expands to the `Rgb` constant of a role in a variant, e.g. `rose_pine!(dawn.pine)` is `palette::dawn::PINE`.
+/// Unknown variants or roles are compile errors.
+#[proc_macro]
+pub fn rose_pine(input: TokenStream) -> TokenStream {
+    let VariantRole { variant, role } = parse_macro_input!(input as VariantRole);
+
+    let Ok(key) = Variant::from_str(&variant.to_string()).map(|v| v.key()) else {
+        let expected = Variant::iter().map(|v| v.key()).collect::<Vec<_>>();
+        return error(variant.span(), "variant", &variant, &expected);
+    };
+    let Ok(name) = Role::from_str(&role.to_string()).map(|r| r.to_string()) else {
+        return error(role.span(), "role", &role, Role::VARIANTS);
+    };
+
+    let module = format_ident!("{key}", span = variant.span());
+    let constant = format_ident!("{}", name.to_uppercase(), span = role.span());
+    quote!(::palette::#module::#constant).into()
+}
+
+fn error(span: Span, kind: &str, name: &Ident, expected: &[impl AsRef<str>]) -> TokenStream {
+    let expected = expected.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+    let message = format!(
+        "unknown {kind} `{name}`, expected one of {}",
+        expected.join(", ")
+    );
+    syn::Error::new(span, message).to_compile_error().into()
+}
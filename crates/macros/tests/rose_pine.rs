@@ -0,0 +1,19 @@
+use palette::{Rgb, Role, Variant};
+use palette_macros::rose_pine;
+
+const PINE: Rgb = rose_pine!(dawn.pine);
+
+#[test]
+fn expands_to_constants() {
+    assert_eq!(PINE, Variant::Dawn.get_rgb(Role::Pine));
+    assert_eq!(rose_pine!(moon.love), palette::moon::LOVE);
+    assert_eq!(
+        rose_pine!(main.highlight_med),
+        Variant::Main.get_rgb(Role::HighlightMed)
+    );
+}
+
+#[test]
+fn unknown_names() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
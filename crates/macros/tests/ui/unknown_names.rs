@@ -0,0 +1,7 @@
+use palette_macros::rose_pine;
+
+fn main() {
+    let _ = rose_pine!(noon.love);
+    let _ = rose_pine!(moon.lovely);
+    let _ = rose_pine!(moon love);
+}
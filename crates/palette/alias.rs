@@ -0,0 +1,135 @@
+use crate::{Color, Role, VariantDefinition};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+/// Semantic name for a role, optionally at an alpha percentage, e.g. `error` for love
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alias {
+    pub role: Role,
+    pub alpha: Option<u16>,
+}
+
+/// Built-in aliases following the Rosé Pine guidelines, a palette file or the cli can override them
+pub const ALIASES: [(&str, Alias); 8] = [
+    ("error", Alias::new(Role::Love, None)),
+    ("warning", Alias::new(Role::Gold, None)),
+    ("info", Alias::new(Role::Foam, None)),
+    ("hint", Alias::new(Role::Iris, None)),
+    ("success", Alias::new(Role::Pine, None)),
+    ("selection", Alias::new(Role::HighlightMed, None)),
+    ("cursor", Alias::new(Role::HighlightHigh, None)),
+    ("search", Alias::new(Role::Rose, Some(20))),
+];
+
+impl Alias {
+    pub const fn new(role: Role, alpha: Option<u16>) -> Self {
+        Self { role, alpha }
+    }
+}
+
+pub fn default_aliases() -> BTreeMap<String, Alias> {
+    ALIASES
+        .into_iter()
+        .map(|(name, alias)| (name.to_string(), alias))
+        .collect()
+}
+
+impl VariantDefinition {
+    /// Color of the role an alias refers to, along with its alpha
+    pub fn resolve_alias(&self, name: &str) -> Option<(Color, Option<u16>)> {
+        self.aliases
+            .get(name)
+            .map(|alias| (self.get_color(alias.role), alias.alpha))
+    }
+}
+
+/// Parses a role name optionally followed by an alpha percentage, e.g. `love` or `rose/20`
+impl FromStr for Alias {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (role, alpha) = match value.split_once('/') {
+            Some((role, alpha)) => (role, Some(alpha)),
+            None => (value, None),
+        };
+
+        let role = Role::from_str(role.trim()).map_err(|_| format!("unknown role `{role}`"))?;
+        let alpha = match alpha.map(|alpha| alpha.trim().parse::<u16>()) {
+            Some(Ok(alpha)) if alpha <= 100 => Some(alpha),
+            Some(_) => return Err(format!("invalid alpha value in `{value}`")),
+            None => None,
+        };
+
+        Ok(Self { role, alpha })
+    }
+}
+
+impl Display for Alias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.role)?;
+        match self.alpha {
+            Some(alpha) => write!(f, "/{alpha}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Serialize for Alias {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Alias {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Deserializes aliases as overrides of the built-in ones
+pub(crate) fn merge_defaults<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, Alias>, D::Error> {
+    let mut aliases = default_aliases();
+    aliases.extend(BTreeMap::<String, Alias>::deserialize(deserializer)?);
+    Ok(aliases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PaletteFile, Variant, BUILTIN};
+
+    #[test]
+    fn parse() {
+        assert_eq!("love".parse(), Ok(Alias::new(Role::Love, None)));
+        assert_eq!(
+            "highlight_med/30".parse(),
+            Ok(Alias::new(Role::HighlightMed, Some(30)))
+        );
+        assert!("lovely".parse::<Alias>().is_err());
+        assert!("love/101".parse::<Alias>().is_err());
+        assert_eq!(Alias::new(Role::Rose, Some(20)).to_string(), "rose/20");
+    }
+
+    #[test]
+    fn overrides() {
+        let content = BUILTIN.replacen(
+            "[variants.colors]",
+            "[variants.aliases]\nerror = \"rose/80\"\n\n[variants.colors]",
+            1,
+        );
+        let file = PaletteFile::from_toml(&content).unwrap();
+
+        let (color, alpha) = file.variants[0].resolve_alias("error").unwrap();
+        assert_eq!(color.rgb, Variant::Main.get_rgb(Role::Rose));
+        assert_eq!(alpha, Some(80));
+
+        let (color, alpha) = file.variants[1].resolve_alias("error").unwrap();
+        assert_eq!(color.rgb, Variant::Moon.get_rgb(Role::Love));
+        assert_eq!(alpha, None);
+        assert_eq!(file.variants[0].aliases.len(), ALIASES.len());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Color, Rgb, Role, Variant, VariantDefinition};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Terminal color slots, the 16 ansi colors in index order followed by the ui colors of a terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AnsiColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    Foreground,
+    Background,
+    Cursor,
+    Selection,
+}
+
+impl AnsiColor {
+    /// Canonical role of the slot, which is the same for every variant
+    pub const fn role(self) -> Role {
+        match self {
+            Self::Black => Role::Overlay,
+            Self::BrightBlack => Role::Muted,
+            Self::Red | Self::BrightRed => Role::Love,
+            Self::Green | Self::BrightGreen => Role::Pine,
+            Self::Yellow | Self::BrightYellow => Role::Gold,
+            Self::Blue | Self::BrightBlue => Role::Foam,
+            Self::Magenta | Self::BrightMagenta => Role::Iris,
+            Self::Cyan | Self::BrightCyan => Role::Rose,
+            Self::White | Self::BrightWhite | Self::Foreground => Role::Text,
+            Self::Background => Role::Base,
+            Self::Cursor => Role::HighlightHigh,
+            Self::Selection => Role::HighlightMed,
+        }
+    }
+
+    /// Index in the 16 color palette, `None` for the ui colors
+    pub fn index(self) -> Option<u8> {
+        Self::iter()
+            .position(|c| c == self)
+            .filter(|i| *i < 16)
+            .map(|i| i as u8)
+    }
+
+    /// The 16 ansi colors in index order
+    pub fn palette() -> impl Iterator<Item = AnsiColor> {
+        Self::iter().take(16)
+    }
+}
+
+impl Variant {
+    pub const fn get_ansi(&self, color: AnsiColor) -> Rgb {
+        self.get_rgb(color.role())
+    }
+}
+
+impl VariantDefinition {
+    pub fn get_ansi(&self, color: AnsiColor) -> Rgb {
+        self.get_rgb(color.role())
+    }
+
+    /// Colors of the 16 ansi slots in index order
+    pub fn ansi_palette(&self) -> Vec<Color> {
+        AnsiColor::palette()
+            .map(|color| Color::from(self.get_ansi(color)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots() {
+        assert_eq!(AnsiColor::Black.index(), Some(0));
+        assert_eq!(AnsiColor::BrightBlack.index(), Some(8));
+        assert_eq!(AnsiColor::BrightWhite.index(), Some(15));
+        assert_eq!(AnsiColor::Cursor.index(), None);
+        assert_eq!(AnsiColor::palette().count(), 16);
+    }
+
+    #[test]
+    fn variant_colors() {
+        assert_eq!(
+            Variant::Main.get_ansi(AnsiColor::Black),
+            Rgb::new(0x26, 0x23, 0x3a)
+        );
+        assert_eq!(
+            Variant::Dawn.get_ansi(AnsiColor::BrightBlack),
+            Rgb::new(0x98, 0x93, 0xa5)
+        );
+
+        let moon = VariantDefinition::from(Variant::Moon).ansi_palette();
+        assert_eq!(moon[2].hex, "3e8fb0");
+        assert_eq!(moon[6].hex, "ea9a97");
+    }
+}
@@ -0,0 +1,115 @@
+use crate::Role;
+#[cfg(feature = "std")]
+use crate::{Color, VariantDefinition};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Groups of roles with the same purpose in a theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RoleCategory {
+    /// base, surface and overlay
+    Background,
+    /// muted, subtle and text
+    Foreground,
+    /// love, gold, rose, pine, foam and iris
+    Accent,
+    /// highlight low, med and high
+    Highlight,
+}
+
+impl RoleCategory {
+    /// Roles of the category in palette order
+    pub fn roles(self) -> impl Iterator<Item = Role> {
+        Role::iter().filter(move |role| role.category() == self)
+    }
+
+    /// Plural name of the category, e.g. `accents`
+    #[cfg(feature = "std")]
+    pub fn plural(self) -> String {
+        format!("{self}s")
+    }
+}
+
+impl Role {
+    pub const fn category(self) -> RoleCategory {
+        match self {
+            Self::Base | Self::Surface | Self::Overlay => RoleCategory::Background,
+            Self::Muted | Self::Subtle | Self::Text => RoleCategory::Foreground,
+            Self::Love | Self::Gold | Self::Rose | Self::Pine | Self::Foam | Self::Iris => {
+                RoleCategory::Accent
+            }
+            Self::HighlightLow | Self::HighlightMed | Self::HighlightHigh => {
+                RoleCategory::Highlight
+            }
+        }
+    }
+
+    pub fn backgrounds() -> impl Iterator<Item = Role> {
+        RoleCategory::Background.roles()
+    }
+
+    pub fn foregrounds() -> impl Iterator<Item = Role> {
+        RoleCategory::Foreground.roles()
+    }
+
+    pub fn accents() -> impl Iterator<Item = Role> {
+        RoleCategory::Accent.roles()
+    }
+
+    pub fn highlights() -> impl Iterator<Item = Role> {
+        RoleCategory::Highlight.roles()
+    }
+}
+
+#[cfg(feature = "std")]
+impl VariantDefinition {
+    /// Roles of a category along with their colors in palette order
+    pub fn category(&self, category: RoleCategory) -> Vec<(Role, Color)> {
+        category
+            .roles()
+            .map(|role| (role, self.get_color(role)))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Variant;
+
+    #[test]
+    fn categories() {
+        assert_eq!(
+            Role::accents().collect::<Vec<_>>(),
+            [
+                Role::Love,
+                Role::Gold,
+                Role::Rose,
+                Role::Pine,
+                Role::Foam,
+                Role::Iris
+            ]
+        );
+        assert_eq!(
+            Role::backgrounds().collect::<Vec<_>>(),
+            [Role::Base, Role::Surface, Role::Overlay]
+        );
+        assert_eq!(Role::HighlightMed.category(), RoleCategory::Highlight);
+        assert_eq!(RoleCategory::Foreground.plural(), "foregrounds");
+
+        let total: usize = RoleCategory::iter().map(|c| c.roles().count()).sum();
+        assert_eq!(total, Role::iter().count());
+    }
+
+    #[test]
+    fn variant_category() {
+        let dawn = VariantDefinition::from(Variant::Dawn);
+        let foregrounds = dawn.category(RoleCategory::Foreground);
+        assert_eq!(foregrounds.len(), 3);
+        assert_eq!(foregrounds[2].0, Role::Text);
+        assert_eq!(foregrounds[2].1.hex, "575279");
+    }
+}
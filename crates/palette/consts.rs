@@ -0,0 +1,91 @@
+/// Declares an `Rgb` constant for every role, taken from a palette in `variant.rs`
+macro_rules! role_constants {
+    ($palette:expr) => {
+        role_constants!($palette;
+            BASE => Base,
+            SURFACE => Surface,
+            OVERLAY => Overlay,
+            MUTED => Muted,
+            SUBTLE => Subtle,
+            TEXT => Text,
+            LOVE => Love,
+            GOLD => Gold,
+            ROSE => Rose,
+            PINE => Pine,
+            FOAM => Foam,
+            IRIS => Iris,
+            HIGHLIGHT_LOW => HighlightLow,
+            HIGHLIGHT_MED => HighlightMed,
+            HIGHLIGHT_HIGH => HighlightHigh,
+        );
+    };
+    ($palette:expr; $($name:ident => $role:ident),* $(,)?) => {
+        $(pub const $name: Rgb = $palette.get_rgb(&Role::$role);)*
+
+        /// Name and value of the constant of a role, e.g. `("LOVE", LOVE)`.
+        /// The match is exhaustive, so a new role doesn't compile without a constant.
+        pub const fn constant(role: Role) -> (&'static str, Rgb) {
+            match role {
+                $(Role::$role => (stringify!($name), $name),)*
+            }
+        }
+    };
+}
+
+/// Declares a module of constants for every variant, e.g. `palette::moon::LOVE`
+macro_rules! variant_modules {
+    ($($module:ident => $variant:ident),* $(,)?) => {$(
+        #[doc = concat!("Colors of the ", stringify!($variant), " variant as constants")]
+        pub mod $module {
+            use crate::{Palette, Rgb, Role, Variant};
+
+            pub const VARIANT: Variant = Variant::$variant;
+            pub const PALETTE: Palette = VARIANT.get_palette();
+            role_constants!(PALETTE);
+        }
+    )*};
+}
+
+variant_modules! {
+    main => Main,
+    moon => Moon,
+    dawn => Dawn,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rgb, Role, Variant};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn constants() {
+        assert_eq!(super::main::BASE, Rgb::new(0x19, 0x17, 0x24));
+        assert_eq!(super::moon::LOVE, Variant::Moon.get_rgb(Role::Love));
+        assert_eq!(super::dawn::PINE, Variant::Dawn.get_rgb(Role::Pine));
+        assert_eq!(
+            super::dawn::HIGHLIGHT_HIGH,
+            Variant::Dawn.get_rgb(Role::HighlightHigh)
+        );
+        assert_eq!(super::moon::VARIANT, Variant::Moon);
+    }
+
+    /// `rose_pine!` expands to the uppercase snake_case name of a role
+    #[test]
+    fn every_role() {
+        for role in Role::iter() {
+            for (variant, constant) in [
+                (Variant::Main, super::main::constant(role)),
+                (Variant::Moon, super::moon::constant(role)),
+                (Variant::Dawn, super::dawn::constant(role)),
+            ] {
+                assert_eq!(
+                    constant,
+                    (
+                        role.to_string().to_uppercase().as_str(),
+                        variant.get_rgb(role)
+                    )
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use crate::{
+    Color, Oklch, Palette, Rgb, Role, RoleCategory, Variant, VariantDefinition, VariantKind,
+};
+use serde::Serialize;
+use strum_macros::Display;
+
+/// Contrast of a foreground color on a background
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Contrast {
+    /// Wcag 2.x contrast ratio, ranging from `1.0` to `21.0`
+    pub ratio: f32,
+    /// Apca lightness contrast, positive for dark text on a light background and negative otherwise
+    pub apca: f32,
+    pub level: WcagLevel,
+}
+
+/// Highest wcag 2.x conformance level met by a contrast ratio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, Serialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WcagLevel {
+    Fail,
+    /// At least 3:1, sufficient for large text and ui components
+    AaLarge,
+    /// At least 4.5:1
+    Aa,
+    /// At least 7:1
+    Aaa,
+}
+
+impl WcagLevel {
+    pub fn from_ratio(ratio: f32) -> Self {
+        match ratio {
+            r if r >= 7.0 => Self::Aaa,
+            r if r >= 4.5 => Self::Aa,
+            r if r >= 3.0 => Self::AaLarge,
+            _ => Self::Fail,
+        }
+    }
+}
+
+impl Contrast {
+    pub fn new(foreground: Rgb, background: Rgb) -> Self {
+        let ratio = foreground.contrast_ratio(background);
+        Self {
+            ratio,
+            apca: foreground.apca_contrast(background),
+            level: WcagLevel::from_ratio(ratio),
+        }
+    }
+}
+
+impl Rgb {
+    /// Relative luminance as defined by wcag 2.x, ranging from `0.0` for black to `1.0` for white
+    pub fn relative_luminance(self) -> f32 {
+        let [r, g, b] = self.to_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Wcag 2.x contrast ratio, the order of the colors doesn't matter
+    pub fn contrast_ratio(self, other: Rgb) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Apca lightness contrast (Lc) of this color as text on `background`, following apca-w3 0.0.98G
+    pub fn apca_contrast(self, background: Rgb) -> f32 {
+        const BLACK_THRESHOLD: f32 = 0.022;
+        const BLACK_CLAMP: f32 = 1.414;
+        const SCALE: f32 = 1.14;
+        const OFFSET: f32 = 0.027;
+        const LOW_CLIP: f32 = 0.1;
+
+        let luminance = |rgb: Rgb| {
+            let [r, g, b] = [rgb.r, rgb.g, rgb.b].map(|c| (f32::from(c) / 255.0).powf(2.4));
+            let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b;
+            match y < BLACK_THRESHOLD {
+                true => y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP),
+                false => y,
+            }
+        };
+        let (text, background) = (luminance(self), luminance(background));
+
+        if (background - text).abs() < 0.0005 {
+            return 0.0;
+        }
+
+        let contrast = if background > text {
+            let sapc = (background.powf(0.56) - text.powf(0.57)) * SCALE;
+            match sapc < LOW_CLIP {
+                true => 0.0,
+                false => sapc - OFFSET,
+            }
+        } else {
+            let sapc = (background.powf(0.65) - text.powf(0.62)) * SCALE;
+            match sapc > -LOW_CLIP {
+                true => 0.0,
+                false => sapc + OFFSET,
+            }
+        };
+
+        contrast * 100.0
+    }
+}
+
+impl Color {
+    pub fn relative_luminance(&self) -> f32 {
+        self.rgb.relative_luminance()
+    }
+
+    pub fn contrast(&self, background: &Color) -> Contrast {
+        Contrast::new(self.rgb, background.rgb)
+    }
+}
+
+impl Variant {
+    pub fn contrast(&self, foreground: Role, background: Role) -> Contrast {
+        Contrast::new(self.get_rgb(foreground), self.get_rgb(background))
+    }
+}
+
+impl VariantDefinition {
+    pub fn contrast(&self, foreground: Role, background: Role) -> Contrast {
+        Contrast::new(self.get_rgb(foreground), self.get_rgb(background))
+    }
+}
+
+/// Minimum wcag contrast ratios of foreground roles on base and surface in a high contrast variant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ContrastTargets {
+    pub text: f32,
+    pub subtle: f32,
+    pub muted: f32,
+    /// Love, gold, rose, pine, foam and iris
+    pub accent: f32,
+}
+
+impl Default for ContrastTargets {
+    fn default() -> Self {
+        Self {
+            text: 7.0,
+            subtle: 7.0,
+            muted: 4.5,
+            accent: 4.5,
+        }
+    }
+}
+
+impl ContrastTargets {
+    /// Target for a foreground role, backgrounds and highlights have none
+    pub fn get(&self, role: Role) -> Option<f32> {
+        match role {
+            Role::Text => Some(self.text),
+            Role::Subtle => Some(self.subtle),
+            Role::Muted => Some(self.muted),
+            _ if role.category() == RoleCategory::Accent => Some(self.accent),
+            _ => None,
+        }
+    }
+
+    /// Sets the target of a `text`, `subtle`, `muted` or `accent` group to a ratio between 1 and 21
+    pub fn set(&mut self, group: &str, ratio: f32) -> Result<(), String> {
+        if !(1.0..=21.0).contains(&ratio) {
+            return Err(format!(
+                "contrast ratio must be between 1 and 21, got {ratio}"
+            ));
+        }
+
+        match group {
+            "text" => self.text = ratio,
+            "subtle" => self.subtle = ratio,
+            "muted" => self.muted = ratio,
+            "accent" => self.accent = ratio,
+            group => {
+                return Err(format!(
+                    "unknown contrast target `{group}`, expected text, subtle, muted or accent"
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Rgb {
+    /// Moves oklch lightness away from the backgrounds just far enough to reach `target` on all of them,
+    /// towards white on dark backgrounds and black on light ones. Hue is kept while chroma is only
+    /// reduced where the color would fall outside of srgb.
+    pub fn with_min_contrast(self, backgrounds: &[Rgb], target: f32, kind: VariantKind) -> Rgb {
+        let contrast = |rgb: Rgb| {
+            backgrounds
+                .iter()
+                .map(|bg| rgb.contrast_ratio(*bg))
+                .fold(f32::INFINITY, f32::min)
+        };
+        if contrast(self) >= target {
+            return self;
+        }
+
+        let color = self.to_oklch();
+        let limit = match kind {
+            VariantKind::Dark => 1.0,
+            VariantKind::Light => 0.0,
+        };
+        let at = |l: f32| Oklch { l, ..color }.to_rgb();
+
+        // search for the smallest change in lightness that meets the target
+        let (mut low, mut high) = (color.l, limit);
+        if contrast(at(high)) < target {
+            return at(high);
+        }
+        for _ in 0..24 {
+            let mid = (low + high) / 2.0;
+            match contrast(at(mid)) >= target {
+                true => high = mid,
+                false => low = mid,
+            }
+        }
+
+        at(high)
+    }
+}
+
+impl VariantDefinition {
+    /// Copy of this variant where foreground and accent roles meet the contrast `targets` on base and surface,
+    /// suffixed with `high-contrast`, e.g. `rose-pine-moon-high-contrast`.
+    /// Custom roles are copied unchanged since they have no category to pick a target from.
+    pub fn high_contrast(&self, targets: &ContrastTargets) -> VariantDefinition {
+        let backgrounds = [self.get_rgb(Role::Base), self.get_rgb(Role::Surface)];
+        let colors = Palette::from_fn(|role| {
+            let rgb = self.get_rgb(role);
+            match targets.get(role) {
+                Some(target) => rgb.with_min_contrast(&backgrounds, target, self.kind),
+                None => rgb,
+            }
+        });
+
+        Self {
+            id: format!("{}-high-contrast", self.id),
+            name: format!("{} High Contrast", self.name),
+            key: format!("{}-high-contrast", self.key),
+            kind: self.kind,
+            description: self.description.clone(),
+            slot: Some(self.slot()),
+            colors,
+            roles: self.roles.clone(),
+            aliases: self.aliases.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    const BLACK: Rgb = Rgb::new(0, 0, 0);
+    const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+    #[test]
+    fn wcag() {
+        assert!((BLACK.contrast_ratio(WHITE) - 21.0).abs() < 0.01);
+        assert!((WHITE.contrast_ratio(WHITE) - 1.0).abs() < 0.01);
+
+        let contrast = Variant::Main.contrast(Role::Text, Role::Base);
+        assert!((contrast.ratio - 13.39).abs() < 0.01);
+        assert_eq!(contrast.level, WcagLevel::Aaa);
+    }
+
+    #[test]
+    fn apca() {
+        assert!((BLACK.apca_contrast(WHITE) - 106.04).abs() < 0.1);
+        assert!((WHITE.apca_contrast(BLACK) + 107.88).abs() < 0.1);
+        assert_eq!(WHITE.apca_contrast(WHITE), 0.0);
+    }
+
+    #[test]
+    fn high_contrast() {
+        let targets = ContrastTargets::default();
+        for variant in Variant::iter() {
+            let definition = VariantDefinition::from(variant);
+            let high_contrast = definition.high_contrast(&targets);
+            assert_eq!(
+                high_contrast.key,
+                format!("{}-high-contrast", variant.key())
+            );
+            assert_eq!(high_contrast.slot(), variant);
+
+            for role in Role::iter() {
+                let original = variant.get_rgb(role);
+                let rgb = high_contrast.get_rgb(role);
+                let Some(target) = targets.get(role) else {
+                    assert_eq!(rgb, original);
+                    continue;
+                };
+
+                for background in [Role::Base, Role::Surface] {
+                    let ratio = rgb.contrast_ratio(variant.get_rgb(background));
+                    assert!(ratio >= target, "{variant} {role} on {background}: {ratio}");
+                }
+                // colors that already meet their target are left alone
+                if original.contrast_ratio(variant.get_rgb(Role::Base)) >= target
+                    && original.contrast_ratio(variant.get_rgb(Role::Surface)) >= target
+                {
+                    assert_eq!(rgb, original);
+                }
+            }
+        }
+
+        // custom roles have no target, even when they are barely visible on base
+        let mut moon = VariantDefinition::from(Variant::Moon);
+        let love_bg = Rgb::new(0x2a, 0x27, 0x3f);
+        moon.roles.insert("love_bg".into(), love_bg);
+        assert_eq!(
+            moon.high_contrast(&targets).roles.get("love_bg"),
+            Some(&love_bg)
+        );
+
+        let dawn = VariantDefinition::from(Variant::Dawn).high_contrast(&targets);
+        let (before, after) = (Variant::Dawn.get_rgb(Role::Gold), dawn.get_rgb(Role::Gold));
+        assert!(after.to_oklch().l < before.to_oklch().l);
+        assert!((after.to_oklch().h - before.to_oklch().h).abs() < 3.0);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{alias, Alias, Color, Metadata, Palette, Rgb, Role, Variant, VariantKind};
+use heck::ToSnakeCase;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
+
+/// Palette file embedded in the crate describing the built-in variants
+pub const BUILTIN: &str = include_str!("rose-pine.toml");
+
+/// A set of variants as read from a palette definition file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaletteFile {
+    pub variants: Vec<VariantDefinition>,
+}
+
+/// A variant whose metadata and colors are only known at runtime
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantDefinition {
+    pub id: String,
+    pub name: String,
+    pub key: String,
+    pub kind: VariantKind,
+    #[serde(default = "default_description")]
+    pub description: String,
+    /// Built-in variant this definition stands in for in `$(main|moon|dawn)` role groups,
+    /// inferred from the key or kind when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<Variant>,
+    pub colors: Palette,
+    /// Additional named colors that can be used like any other role
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty", with = "hex_map")]
+    pub roles: BTreeMap<String, Rgb>,
+    /// Semantic names for roles, overriding the built-in [`ALIASES`](crate::ALIASES)
+    #[serde(
+        default = "alias::default_aliases",
+        deserialize_with = "alias::merge_defaults"
+    )]
+    pub aliases: BTreeMap<String, Alias>,
+}
+
+fn default_description() -> String {
+    env!("CARGO_PKG_DESCRIPTION").to_string()
+}
+
+impl PaletteFile {
+    pub fn from_toml(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(content)
+    }
+
+    /// The built-in Main, Moon and Dawn variants
+    pub fn builtin() -> Self {
+        Self::from_toml(BUILTIN).expect("embedded palette file is valid")
+    }
+}
+
+impl VariantDefinition {
+    pub fn slot(&self) -> Variant {
+        self.slot
+            .or_else(|| Variant::iter().find(|v| v.key() == self.key))
+            .unwrap_or(match self.kind {
+                VariantKind::Dark => Variant::Main,
+                VariantKind::Light => Variant::Dawn,
+            })
+    }
+
+    pub fn get_rgb(&self, role: Role) -> Rgb {
+        self.colors.get_rgb(&role)
+    }
+
+    pub fn get_color(&self, role: Role) -> Color {
+        Color::from(self.get_rgb(role))
+    }
+
+    /// Looks up a color defined in the additional roles of this variant
+    pub fn get_custom_color(&self, name: &str) -> Option<Color> {
+        self.roles.get(name).copied().map(Color::from)
+    }
+
+    /// Looks up a built-in or custom role by its snake case name
+    pub fn find_color(&self, name: &str) -> Option<Color> {
+        match Role::from_str(name) {
+            Ok(role) => Some(self.get_color(role)),
+            Err(_) => self.get_custom_color(name),
+        }
+    }
+
+    pub fn metadata(&self) -> HashMap<String, String> {
+        Metadata::iter()
+            .map(|r| (r.to_string().to_snake_case(), r.format(self)))
+            .collect()
+    }
+
+    pub fn colors(&self) -> HashMap<String, Color> {
+        Role::iter()
+            .map(|r| (r.to_string().to_snake_case(), self.get_color(r)))
+            .chain(
+                self.roles
+                    .iter()
+                    .map(|(name, rgb)| (name.clone(), Color::from(*rgb))),
+            )
+            .collect()
+    }
+}
+
+impl From<Variant> for VariantDefinition {
+    fn from(variant: Variant) -> Self {
+        Self {
+            id: variant.id().into(),
+            name: variant.name().into(),
+            key: variant.key().into(),
+            kind: variant.kind(),
+            description: default_description(),
+            slot: Some(variant),
+            colors: variant.get_palette(),
+            roles: BTreeMap::new(),
+            aliases: alias::default_aliases(),
+        }
+    }
+}
+
+impl Serialize for Palette {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            Role::iter().map(|role| (role.to_string(), format!("#{}", self.get_rgb(&role).hex()))),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for Palette {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut values = HashMap::<String, String>::deserialize(deserializer)?;
+
+        let mut colors = HashMap::new();
+        for role in Role::iter() {
+            let value = values
+                .remove(&role.to_string())
+                .ok_or_else(|| de::Error::custom(format!("missing value for role `{role}`")))?;
+            let rgb = Rgb::from_hex(&value)
+                .ok_or_else(|| de::Error::custom(format!("invalid hex color `{value}`")))?;
+            colors.insert(role, rgb);
+        }
+
+        if let Some(key) = values.keys().next() {
+            return Err(de::Error::custom(format!("unknown role `{key}`")));
+        }
+
+        Ok(Palette::from_fn(|role| colors[&role]))
+    }
+}
+
+mod hex_map {
+    use crate::Rgb;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<S: Serializer>(
+        value: &BTreeMap<String, Rgb>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(value.iter().map(|(k, v)| (k, format!("#{}", v.hex()))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<String, Rgb>, D::Error> {
+        BTreeMap::<String, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, value)| match Rgb::from_hex(&value) {
+                Some(rgb) => Ok((name, rgb)),
+                None => Err(de::Error::custom(format!("invalid hex color `{value}`"))),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_matches_consts() {
+        let file = PaletteFile::builtin();
+        assert_eq!(file.variants.len(), Variant::iter().count());
+
+        for (definition, variant) in file.variants.iter().zip(Variant::iter()) {
+            let expected = VariantDefinition::from(variant);
+            assert_eq!(definition.metadata(), expected.metadata());
+            assert_eq!(definition.slot(), variant);
+            for role in Role::iter() {
+                assert_eq!(definition.get_rgb(role), variant.get_rgb(role));
+            }
+        }
+    }
+
+    #[test]
+    fn missing_role() {
+        let content = BUILTIN.replacen("base = \"#191724\"\n", "", 1);
+        let err = PaletteFile::from_toml(&content).unwrap_err();
+        assert!(err.message().contains("missing value for role `base`"));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let file = PaletteFile::builtin();
+        let json = serde_json::to_string(&file).unwrap();
+        let parsed = PaletteFile::from_json(&json).unwrap();
+        assert_eq!(
+            parsed.variants[1].get_rgb(Role::Pine),
+            Rgb::new(62, 143, 176)
+        );
+    }
+}
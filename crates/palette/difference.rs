@@ -0,0 +1,175 @@
+use crate::{Lab, Oklab, Rgb, Role, Variant, VariantDefinition};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Perceptual color difference metric
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, EnumIter, EnumString, Serialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DeltaE {
+    /// Cie ΔE 2000, a difference of about `1.0` is just noticeable
+    #[default]
+    Ciede2000,
+    /// Euclidean distance in oklab, a difference of about `0.02` is just noticeable
+    Ok,
+}
+
+impl DeltaE {
+    pub fn distance(self, a: Rgb, b: Rgb) -> f32 {
+        match self {
+            Self::Ciede2000 => a.to_lab().delta_e_2000(b.to_lab()),
+            Self::Ok => a.to_oklab().delta_e(b.to_oklab()),
+        }
+    }
+}
+
+impl Lab {
+    /// Cie ΔE 2000 difference with unit weighting factors
+    pub fn delta_e_2000(self, other: Lab) -> f32 {
+        let (l1, a1, b1) = (self.l as f64, self.a as f64, self.b as f64);
+        let (l2, a2, b2) = (other.l as f64, other.a as f64, other.b as f64);
+
+        let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+        let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt());
+        let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+        let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+        let hue = |a: f64, b: f64| match a == 0.0 && b == 0.0 {
+            true => 0.0,
+            false => b.atan2(a).to_degrees().rem_euclid(360.0),
+        };
+        let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+        let delta_l = l2 - l1;
+        let delta_c = c2 - c1;
+        let delta_h = match c1 * c2 == 0.0 {
+            true => 0.0,
+            false => match h2 - h1 {
+                d if d > 180.0 => d - 360.0,
+                d if d < -180.0 => d + 360.0,
+                d => d,
+            },
+        };
+        let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+        let l_mean = (l1 + l2) / 2.0;
+        let c_mean = (c1 + c2) / 2.0;
+        let h_mean = match (c1 * c2 == 0.0, (h1 - h2).abs() > 180.0) {
+            (true, _) => h1 + h2,
+            (false, false) => (h1 + h2) / 2.0,
+            (false, true) if h1 + h2 < 360.0 => (h1 + h2 + 360.0) / 2.0,
+            (false, true) => (h1 + h2 - 360.0) / 2.0,
+        };
+
+        let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+            + 0.24 * (2.0 * h_mean).to_radians().cos()
+            + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+            - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+        let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+        let s_c = 1.0 + 0.045 * c_mean;
+        let s_h = 1.0 + 0.015 * c_mean * t;
+        let r_t = -2.0
+            * (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt()
+            * (60.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp())
+                .to_radians()
+                .sin();
+
+        let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+        (l * l + c * c + h * h + r_t * c * h).sqrt() as f32
+    }
+}
+
+impl Oklab {
+    /// Euclidean distance to another oklab color
+    pub fn delta_e(self, other: Oklab) -> f32 {
+        let (l, a, b) = (self.l - other.l, self.a - other.a, self.b - other.b);
+        (l * l + a * a + b * b).sqrt()
+    }
+}
+
+impl Rgb {
+    pub fn delta_e(self, other: Rgb, metric: DeltaE) -> f32 {
+        metric.distance(self, other)
+    }
+}
+
+/// Role closest to `color` among the given role colors
+fn nearest(color: Rgb, metric: DeltaE, get_rgb: impl Fn(Role) -> Rgb) -> (Role, f32) {
+    Role::iter()
+        .map(|role| (role, metric.distance(color, get_rgb(role))))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .expect("there is at least one role")
+}
+
+impl Variant {
+    /// Role perceptually closest to `color` by [`DeltaE::Ciede2000`], along with its distance
+    pub fn nearest_role(&self, color: Rgb) -> (Role, f32) {
+        self.nearest_role_by(color, DeltaE::default())
+    }
+
+    pub fn nearest_role_by(&self, color: Rgb, metric: DeltaE) -> (Role, f32) {
+        nearest(color, metric, |role| self.get_rgb(role))
+    }
+}
+
+impl VariantDefinition {
+    /// Role perceptually closest to `color` by [`DeltaE::Ciede2000`], along with its distance
+    pub fn nearest_role(&self, color: Rgb) -> (Role, f32) {
+        self.nearest_role_by(color, DeltaE::default())
+    }
+
+    pub fn nearest_role_by(&self, color: Rgb, metric: DeltaE) -> (Role, f32) {
+        nearest(color, metric, |role| self.get_rgb(role))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ciede2000_reference() {
+        // pairs from Sharma, Wu and Dalal's ciede2000 test data
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 2.5, 0.0), (50.0, 0.0, -2.5), 4.3065),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+            ((2.0776, 0.0795, -1.135), (0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+
+        for ((l1, a1, b1), (l2, a2, b2), expected) in pairs {
+            let a = Lab {
+                l: l1,
+                a: a1,
+                b: b1,
+            };
+            let b = Lab {
+                l: l2,
+                a: a2,
+                b: b2,
+            };
+            let delta = a.delta_e_2000(b);
+            assert!((delta - expected).abs() < 1e-3, "{delta} != {expected}");
+            assert!((b.delta_e_2000(a) - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn nearest_role() {
+        let (role, distance) = Variant::Main.nearest_role(Rgb::new(0xe0, 0x60, 0x7f));
+        assert_eq!(role, Role::Love);
+        assert!(distance < 5.0);
+
+        let (role, distance) = Variant::Dawn.nearest_role(Variant::Dawn.get_rgb(Role::Pine));
+        assert_eq!(role, Role::Pine);
+        assert_eq!(distance, 0.0);
+
+        let (role, _) = Variant::Moon.nearest_role_by(Rgb::new(0x3e, 0x8f, 0xb1), DeltaE::Ok);
+        assert_eq!(role, Role::Pine);
+    }
+}
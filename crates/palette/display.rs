@@ -0,0 +1,92 @@
+use crate::{Hsl, Rgb};
+use core::fmt::{self, Display, Formatter, LowerHex, UpperHex, Write};
+
+/// Hex color with a leading `#`, e.g. `#ebbcba`
+impl Display for Rgb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "#{self:x}")
+    }
+}
+
+/// Hex digits without a leading `#`, e.g. `ebbcba`
+impl LowerHex for Rgb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Hex digits without a leading `#`, e.g. `EBBCBA`
+impl UpperHex for Rgb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+}
+
+/// Css hsl function rounded to the formatter precision, e.g. `hsl(2, 55%, 83%)` or with `{:.1}` `hsl(2.4, 55.1%, 82.5%)`
+impl Display for Hsl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(0);
+        write!(
+            f,
+            "hsl({:.precision$}, {:.precision$}%, {:.precision$}%)",
+            self.h, self.s, self.l
+        )
+    }
+}
+
+/// Writes formatted text into a caller supplied buffer, for use without an allocator, e.g.
+/// `format_into(&mut buf, format_args!("{}", Variant::Moon.get_rgb(Role::Love)))`.
+/// Fails when the buffer is too small.
+pub fn format_into<'a>(buf: &'a mut [u8], args: fmt::Arguments) -> Result<&'a str, fmt::Error> {
+    let mut writer = BufWriter { buf, len: 0 };
+    writer.write_fmt(args)?;
+    let BufWriter { buf, len } = writer;
+    // only whole strs are copied into the buffer
+    core::str::from_utf8(&buf[..len]).map_err(|_| fmt::Error)
+}
+
+struct BufWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for BufWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Role, Variant};
+
+    #[test]
+    fn display() {
+        let rose = Variant::Main.get_rgb(Role::Rose);
+        let mut buf = [0; 32];
+        assert_eq!(format_into(&mut buf, format_args!("{rose}")), Ok("#ebbcba"));
+        assert_eq!(
+            format_into(&mut buf, format_args!("{rose:X}")),
+            Ok("EBBCBA")
+        );
+        assert_eq!(
+            format_into(&mut buf, format_args!("{}", rose.to_hsl())),
+            Ok("hsl(2, 55%, 83%)")
+        );
+        assert_eq!(
+            format_into(&mut buf, format_args!("{:.1}", rose.to_hsl())),
+            Ok("hsl(2.4, 55.1%, 82.5%)")
+        );
+        assert_eq!(
+            format_into(&mut [0; 6], format_args!("{rose}")),
+            Err(fmt::Error)
+        );
+    }
+}
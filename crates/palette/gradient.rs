@@ -0,0 +1,210 @@
+use crate::{Color, Oklab, Oklch, Palette, Rgb, Role, VariantDefinition, VariantKind};
+use heck::ToTitleCase;
+use serde::Serialize;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Color space in which gradients are interpolated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, EnumIter, EnumString, Serialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Interpolation {
+    /// Gamma encoded srgb channels, like most image editors
+    Srgb,
+    /// Perceptually even steps without hue shifts
+    #[default]
+    Oklab,
+    /// Perceptually even steps along the shorter hue arc, keeping intermediate colors vivid
+    Oklch,
+}
+
+impl Rgb {
+    /// Color at `t` between this color (`0.0`) and `other` (`1.0`)
+    pub fn interpolate(self, other: Rgb, t: f32, space: Interpolation) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+        match space {
+            Interpolation::Srgb => {
+                let channel = |a: u8, b: u8| lerp(a.into(), b.into()).round() as u8;
+                Rgb::new(
+                    channel(self.r, other.r),
+                    channel(self.g, other.g),
+                    channel(self.b, other.b),
+                )
+            }
+            Interpolation::Oklab => {
+                let (a, b) = (self.to_oklab(), other.to_oklab());
+                Oklab {
+                    l: lerp(a.l, b.l),
+                    a: lerp(a.a, b.a),
+                    b: lerp(a.b, b.b),
+                }
+                .to_rgb()
+            }
+            Interpolation::Oklch => {
+                let (mut a, mut b) = (self.to_oklch(), other.to_oklch());
+                // an achromatic end has no hue of its own, so it takes the hue of the other end
+                if a.c < 1e-4 {
+                    a.h = b.h;
+                }
+                if b.c < 1e-4 {
+                    b.h = a.h;
+                }
+                let hue = match b.h - a.h {
+                    d if d > 180.0 => d - 360.0,
+                    d if d < -180.0 => d + 360.0,
+                    d => d,
+                };
+
+                Oklch {
+                    l: lerp(a.l, b.l),
+                    c: lerp(a.c, b.c),
+                    h: (a.h + hue * t).rem_euclid(360.0),
+                }
+                .to_rgb()
+            }
+        }
+    }
+
+    /// `steps` evenly spaced colors from this color to `other`, both included
+    pub fn ramp(self, other: Rgb, steps: usize, space: Interpolation) -> Vec<Rgb> {
+        match steps {
+            0 => vec![],
+            1 => vec![self],
+            _ => (0..steps)
+                .map(|i| self.interpolate(other, i as f32 / (steps - 1) as f32, space))
+                .collect(),
+        }
+    }
+}
+
+impl Color {
+    pub fn interpolate(&self, other: &Color, t: f32, space: Interpolation) -> Color {
+        self.rgb.interpolate(other.rgb, t, space).into()
+    }
+
+    pub fn ramp(&self, other: &Color, steps: usize, space: Interpolation) -> Vec<Color> {
+        self.rgb
+            .ramp(other.rgb, steps, space)
+            .into_iter()
+            .map(Color::from)
+            .collect()
+    }
+}
+
+impl VariantDefinition {
+    /// Synthesizes a variant between this one (`0.0`) and `other` (`100.0`) by interpolating every role.
+    /// The id and name are derived from this variant with its key replaced by `key`, the kind follows
+    /// the lightness of the interpolated base and role groups use the slot of the closer variant.
+    pub fn interpolate(
+        &self,
+        other: &VariantDefinition,
+        ratio: f32,
+        space: Interpolation,
+        key: &str,
+    ) -> VariantDefinition {
+        let t = ratio / 100.0;
+        let colors = Palette::from_fn(|role| {
+            self.get_rgb(role)
+                .interpolate(other.get_rgb(role), t, space)
+        });
+        let kind = match colors.get_rgb(&Role::Base).to_oklab().l < 0.5 {
+            true => VariantKind::Dark,
+            false => VariantKind::Light,
+        };
+
+        let rename = |value: &str, old: &str, new: &str| match value.strip_suffix(old) {
+            Some(prefix) => format!("{prefix}{new}"),
+            None => format!("{value} {new}"),
+        };
+
+        Self {
+            id: rename(&self.id, &self.key, key).replace(' ', "-"),
+            name: rename(&self.name, &self.key.to_title_case(), &key.to_title_case()),
+            key: key.to_string(),
+            kind,
+            description: self.description.clone(),
+            slot: Some(match t < 0.5 {
+                true => self.slot(),
+                false => other.slot(),
+            }),
+            colors,
+            roles: self
+                .roles
+                .iter()
+                .filter_map(|(name, rgb)| {
+                    let other = other.roles.get(name)?;
+                    Some((name.clone(), rgb.interpolate(*other, t, space)))
+                })
+                .collect(),
+            aliases: self.aliases.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn ramp_ends() {
+        let pine = Variant::Main.get_rgb(Role::Pine);
+        let love = Variant::Main.get_rgb(Role::Love);
+
+        for space in Interpolation::iter() {
+            let ramp = pine.ramp(love, 5, space);
+            assert_eq!(ramp.len(), 5);
+            assert_eq!(ramp[0], pine);
+            assert_eq!(ramp[4], love);
+        }
+        assert_eq!(pine.ramp(love, 1, Interpolation::Oklab), [pine]);
+        assert!(pine.ramp(love, 0, Interpolation::Oklab).is_empty());
+    }
+
+    #[test]
+    fn interpolate() {
+        let black = Rgb::new(0, 0, 0);
+        let white = Rgb::new(255, 255, 255);
+        assert_eq!(
+            black.interpolate(white, 0.5, Interpolation::Srgb),
+            Rgb::new(128, 128, 128)
+        );
+        assert_eq!(
+            black.interpolate(white, 0.5, Interpolation::Oklab),
+            black.interpolate(white, 0.5, Interpolation::Oklch)
+        );
+
+        // the oklch midpoint of red and blue keeps its chroma while oklab passes closer to gray
+        let red = Rgb::new(255, 0, 0);
+        let blue = Rgb::new(0, 0, 255);
+        let oklab = red.interpolate(blue, 0.5, Interpolation::Oklab);
+        let oklch = red.interpolate(blue, 0.5, Interpolation::Oklch);
+        assert!(oklch.to_oklch().c > oklab.to_oklch().c);
+    }
+
+    #[test]
+    fn interpolated_variant() {
+        let moon = VariantDefinition::from(Variant::Moon);
+        let dawn = VariantDefinition::from(Variant::Dawn);
+
+        let dusk = moon.interpolate(&dawn, 30.0, Interpolation::Oklch, "dusk");
+        assert_eq!(dusk.id, "rose-pine-dusk");
+        assert_eq!(dusk.name, "Rosé Pine Dusk");
+        assert_eq!(dusk.kind, VariantKind::Dark);
+        assert_eq!(dusk.slot(), Variant::Moon);
+
+        let main = VariantDefinition::from(Variant::Main);
+        let noon = main.interpolate(&dawn, 80.0, Interpolation::Oklab, "noon");
+        assert_eq!(noon.id, "rose-pine-noon");
+        assert_eq!(noon.name, "Rosé Pine Noon");
+        assert_eq!(noon.kind, VariantKind::Light);
+        assert_eq!(noon.slot(), Variant::Dawn);
+
+        let same = moon.interpolate(&dawn, 0.0, Interpolation::Oklab, "moon");
+        for role in Role::iter() {
+            assert_eq!(same.get_rgb(role), moon.get_rgb(role));
+        }
+    }
+}
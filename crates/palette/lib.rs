@@ -1,17 +1,90 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+pub mod alias;
+#[cfg(feature = "std")]
+pub mod ansi;
+pub mod category;
+pub mod consts;
+#[cfg(feature = "std")]
+pub mod contrast;
+#[cfg(feature = "std")]
+pub mod definition;
+#[cfg(feature = "std")]
+pub mod difference;
+pub mod display;
+#[cfg(feature = "std")]
+pub mod gradient;
+#[cfg(feature = "std")]
+pub mod manipulate;
+pub mod named;
+#[cfg(feature = "std")]
+pub mod parse;
+#[cfg(feature = "std")]
+pub mod scale;
+#[cfg(feature = "std")]
+pub mod schema;
+#[cfg(feature = "std")]
+pub mod simulate;
+#[cfg(feature = "std")]
+pub mod space;
 pub mod variant;
+pub mod version;
+#[cfg(feature = "std")]
+pub mod xterm;
+#[cfg(feature = "std")]
+pub use alias::*;
+#[cfg(feature = "std")]
+pub use ansi::*;
+pub use category::*;
+pub use consts::*;
+#[cfg(feature = "std")]
+pub use contrast::*;
+#[cfg(feature = "std")]
+pub use definition::*;
+#[cfg(feature = "std")]
+pub use difference::*;
+pub use display::*;
+#[cfg(feature = "std")]
+pub use gradient::*;
+pub use named::*;
+#[cfg(feature = "std")]
+pub use parse::*;
+#[cfg(feature = "std")]
+pub use scale::*;
+#[cfg(feature = "std")]
+pub use schema::*;
+#[cfg(feature = "std")]
+pub use simulate::*;
+#[cfg(feature = "std")]
+pub use space::*;
 pub use variant::*;
+pub use version::*;
+#[cfg(feature = "std")]
+pub use xterm::*;
 
 use serde::Serialize;
 use strum_macros::{Display, EnumIter, EnumString, VariantNames};
 
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Serialize)]
 pub struct Color {
     pub rgb: Rgb,
     pub hsl: Hsl,
+    pub hsv: Hsv,
+    pub hwb: Hwb,
+    pub xyz: Xyz,
+    pub lab: Lab,
+    pub lch: Lch,
+    pub oklab: Oklab,
+    pub oklch: Oklch,
     pub hex: String,
+    /// Opacity percentage of a parsed color, colors of roles are opaque
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alpha: Option<f32>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct Rgb {
     pub r: u8,
     pub g: u8,
@@ -25,24 +98,167 @@ impl From<Rgb> for (u8, u8, u8) {
 }
 
 impl Rgb {
-    pub fn new(r: u8, g: u8, b: u8) -> Self {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
         Self { r, g, b }
     }
+
+    /// Parses a 6 digit hex color, the leading `#` is optional
+    pub fn from_hex(value: &str) -> Option<Self> {
+        let value = value.strip_prefix('#').unwrap_or(value);
+        if value.len() != 6 || !value.is_ascii() {
+            return None;
+        }
+
+        let channel = |i: usize| u8::from_str_radix(&value[i..i + 2], 16).ok();
+        Some(Self::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn hex(&self) -> String {
+        format!("{self:x}")
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg(feature = "std")]
+impl From<Rgb> for Color {
+    fn from(rgb: Rgb) -> Self {
+        Self {
+            rgb,
+            hsl: rgb.to_hsl(),
+            hsv: rgb.to_hsv(),
+            hwb: rgb.to_hwb(),
+            xyz: rgb.to_xyz(),
+            lab: rgb.to_lab(),
+            lch: rgb.to_lch(),
+            oklab: rgb.to_oklab(),
+            oklch: rgb.to_oklch(),
+            hex: rgb.hex(),
+            alpha: None,
+        }
+    }
+}
+
+/// Hue in degrees, saturation and lightness in percentages
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct Hsl {
-    pub h: u16,
-    pub s: u8,
-    pub l: u8,
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
 }
 
 impl Hsl {
-    pub fn new(h: u16, s: u8, l: u8) -> Self {
+    pub const fn new(h: f32, s: f32, l: f32) -> Self {
         Self { h, s, l }
     }
 }
 
+impl From<Rgb> for Hsl {
+    fn from(rgb: Rgb) -> Self {
+        rgb.to_hsl()
+    }
+}
+
+/// Hue in degrees, saturation and value in percentages
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Hsv {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+impl Hsv {
+    pub const fn new(h: f32, s: f32, v: f32) -> Self {
+        Self { h, s, v }
+    }
+}
+
+impl From<Rgb> for Hsv {
+    fn from(rgb: Rgb) -> Self {
+        rgb.to_hsv()
+    }
+}
+
+/// Hue in degrees, whiteness and blackness in percentages
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Hwb {
+    pub h: f32,
+    pub w: f32,
+    pub b: f32,
+}
+
+impl Hwb {
+    pub const fn new(h: f32, w: f32, b: f32) -> Self {
+        Self { h, w, b }
+    }
+}
+
+impl From<Rgb> for Hwb {
+    fn from(rgb: Rgb) -> Self {
+        rgb.to_hwb()
+    }
+}
+
+impl Rgb {
+    /// Channels scaled to `0.0..=1.0` along with their max and min
+    const fn unit_channels(self) -> (f32, f32, f32, f32, f32) {
+        let (r, g, b) = (
+            self.r as f32 / 255.0,
+            self.g as f32 / 255.0,
+            self.b as f32 / 255.0,
+        );
+        (r, g, b, r.max(g).max(b), r.min(g).min(b))
+    }
+
+    /// Hue in degrees shared by the hsl, hsv and hwb representations
+    const fn hue(self) -> f32 {
+        let (r, g, b, max, min) = self.unit_channels();
+        let delta = max - min;
+        if delta == 0.0 {
+            return 0.0;
+        }
+
+        let h = if max == r {
+            (g - b) / delta
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+
+        if h < 0.0 {
+            (h + 6.0) * 60.0
+        } else {
+            h * 60.0
+        }
+    }
+
+    pub const fn to_hsl(self) -> Hsl {
+        let (_, _, _, max, min) = self.unit_channels();
+        let delta = max - min;
+        let l = (max + min) / 2.0;
+        let s = if delta == 0.0 {
+            0.0
+        } else {
+            delta / (1.0 - (2.0 * l - 1.0).abs())
+        };
+
+        Hsl::new(self.hue(), s * 100.0, l * 100.0)
+    }
+
+    pub const fn to_hsv(self) -> Hsv {
+        let (_, _, _, max, min) = self.unit_channels();
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+        Hsv::new(self.hue(), s * 100.0, max * 100.0)
+    }
+
+    pub const fn to_hwb(self) -> Hwb {
+        let (_, _, _, max, min) = self.unit_channels();
+
+        Hwb::new(self.hue(), min * 100.0, (1.0 - max) * 100.0)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Display, EnumIter)]
 pub enum Metadata {
     Id,
@@ -52,14 +268,15 @@ pub enum Metadata {
     Kind,
 }
 
+#[cfg(feature = "std")]
 impl Metadata {
-    pub fn format(&self, variant: &Variant) -> String {
+    pub fn format(&self, variant: &VariantDefinition) -> String {
         match self {
-            Self::Id => variant.id(),
-            Self::Name => variant.name(),
-            Self::Description => env!("CARGO_PKG_DESCRIPTION").to_string(),
-            Self::Key => variant.key(),
-            Self::Kind => variant.kind().to_string(),
+            Self::Id => variant.id.clone(),
+            Self::Name => variant.name.clone(),
+            Self::Description => variant.description.clone(),
+            Self::Key => variant.key.clone(),
+            Self::Kind => variant.kind.to_string(),
         }
     }
 }
@@ -94,12 +311,8 @@ impl Role {
         variant.get_hsl(*self)
     }
 
+    #[cfg(feature = "std")]
     pub fn get_color(&self, v: &Variant) -> Color {
-        let rgb = self.get_rgb(v);
-        Color {
-            rgb,
-            hsl: self.get_hsl(v),
-            hex: format!("{:02x}{:02x}{:02x}", rgb.r, rgb.g, rgb.b),
-        }
+        Color::from(self.get_rgb(v))
     }
 }
@@ -0,0 +1,164 @@
+use crate::{Color, Oklab, Oklch, Rgb};
+
+/// Color adjustments, performed in oklch so lightness and chroma changes are perceptually even.
+/// Amounts are percentages unless stated otherwise.
+impl Rgb {
+    /// Raises oklch lightness by `amount` percentage points
+    pub fn lighten(self, amount: f32) -> Rgb {
+        self.map_oklch(|c| Oklch {
+            l: (c.l + amount / 100.0).clamp(0.0, 1.0),
+            ..c
+        })
+    }
+
+    /// Lowers oklch lightness by `amount` percentage points
+    pub fn darken(self, amount: f32) -> Rgb {
+        self.lighten(-amount)
+    }
+
+    /// Scales chroma up by `amount` percent
+    pub fn saturate(self, amount: f32) -> Rgb {
+        self.map_oklch(|c| Oklch {
+            c: (c.c * (1.0 + amount / 100.0)).max(0.0),
+            ..c
+        })
+    }
+
+    /// Scales chroma down by `amount` percent
+    pub fn desaturate(self, amount: f32) -> Rgb {
+        self.saturate(-amount)
+    }
+
+    /// Mixes in `ratio` percent of `other`, interpolated in oklab
+    pub fn mix(self, other: Rgb, ratio: f32) -> Rgb {
+        let t = (ratio / 100.0).clamp(0.0, 1.0);
+        let (a, b) = (self.to_oklab(), other.to_oklab());
+
+        Oklab {
+            l: a.l + (b.l - a.l) * t,
+            a: a.a + (b.a - a.a) * t,
+            b: a.b + (b.b - a.b) * t,
+        }
+        .to_rgb()
+    }
+
+    /// Rotates the oklch hue by `degrees`
+    pub fn hue_rotate(self, degrees: f32) -> Rgb {
+        self.map_oklch(|c| Oklch {
+            h: (c.h + degrees).rem_euclid(360.0),
+            ..c
+        })
+    }
+
+    /// Inverts every rgb channel
+    pub fn invert(self) -> Rgb {
+        Rgb::new(255 - self.r, 255 - self.g, 255 - self.b)
+    }
+
+    /// Removes all chroma while keeping the perceived lightness
+    pub fn grayscale(self) -> Rgb {
+        self.map_oklch(|c| Oklch { c: 0.0, ..c })
+    }
+
+    fn map_oklch(self, f: impl FnOnce(Oklch) -> Oklch) -> Rgb {
+        f(self.to_oklch()).to_rgb()
+    }
+}
+
+impl Rgb {
+    /// Composites this color at `alpha` percent opacity over an opaque `background`.
+    /// Blending happens on the srgb channels like most applications do, so the result
+    /// looks the same as the translucent color would.
+    pub fn flatten(self, background: Rgb, alpha: f32) -> Rgb {
+        let alpha = (alpha / 100.0).clamp(0.0, 1.0);
+        let blend =
+            |fg: u8, bg: u8| (f32::from(fg) * alpha + f32::from(bg) * (1.0 - alpha)).round() as u8;
+
+        Rgb::new(
+            blend(self.r, background.r),
+            blend(self.g, background.g),
+            blend(self.b, background.b),
+        )
+    }
+}
+
+impl Color {
+    pub fn lighten(&self, amount: f32) -> Color {
+        self.rgb.lighten(amount).into()
+    }
+
+    pub fn darken(&self, amount: f32) -> Color {
+        self.rgb.darken(amount).into()
+    }
+
+    pub fn saturate(&self, amount: f32) -> Color {
+        self.rgb.saturate(amount).into()
+    }
+
+    pub fn desaturate(&self, amount: f32) -> Color {
+        self.rgb.desaturate(amount).into()
+    }
+
+    pub fn mix(&self, other: &Color, ratio: f32) -> Color {
+        self.rgb.mix(other.rgb, ratio).into()
+    }
+
+    pub fn hue_rotate(&self, degrees: f32) -> Color {
+        self.rgb.hue_rotate(degrees).into()
+    }
+
+    pub fn invert(&self) -> Color {
+        self.rgb.invert().into()
+    }
+
+    pub fn grayscale(&self) -> Color {
+        self.rgb.grayscale().into()
+    }
+
+    pub fn flatten(&self, background: &Color, alpha: f32) -> Color {
+        self.rgb.flatten(background.rgb, alpha).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rgb, Role, Variant};
+
+    #[test]
+    fn mix() {
+        let love = Variant::Main.get_rgb(Role::Love);
+        let base = Variant::Main.get_rgb(Role::Base);
+        assert_eq!(love.mix(base, 0.0), love);
+        assert_eq!(love.mix(base, 100.0), base);
+        assert_eq!(love.mix(base, 50.0), base.mix(love, 50.0));
+    }
+
+    #[test]
+    fn flatten() {
+        let love = Variant::Main.get_rgb(Role::Love);
+        let base = Variant::Main.get_rgb(Role::Base);
+        assert_eq!(love.flatten(base, 100.0), love);
+        assert_eq!(love.flatten(base, 0.0), base);
+        assert_eq!(love.flatten(base, 20.0), Rgb::new(67, 41, 58));
+    }
+
+    #[test]
+    fn lightness() {
+        let pine = Variant::Moon.get_rgb(Role::Pine);
+        assert!(pine.lighten(10.0).to_oklch().l > pine.to_oklch().l);
+        assert!(pine.darken(10.0).to_oklch().l < pine.to_oklch().l);
+        assert_eq!(pine.lighten(100.0), Rgb::new(255, 255, 255));
+        assert_eq!(pine.darken(100.0), Rgb::new(0, 0, 0));
+    }
+
+    #[test]
+    fn hue_and_chroma() {
+        let iris = Variant::Dawn.get_rgb(Role::Iris);
+        assert_eq!(iris.hue_rotate(360.0), iris);
+        assert!(iris.desaturate(50.0).to_oklch().c < iris.to_oklch().c);
+
+        let gray = iris.grayscale();
+        assert!(gray.r.abs_diff(gray.g) <= 1 && gray.g.abs_diff(gray.b) <= 1);
+        assert_eq!(iris.invert().invert(), iris);
+    }
+}
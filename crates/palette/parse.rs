@@ -0,0 +1,627 @@
+use crate::{
+    Color, Hsl, Hwb, Lab, Lch, Oklab, Oklch, Palette, Rgb, Variant, VariantKind, XtermPalette,
+};
+use serde::{
+    de::{self, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{fmt::Display, marker::PhantomData, str::FromStr};
+
+/// Parses a variant by its id, key or name ignoring case, e.g. `rose-pine-moon`, `moon` or `Rosé Pine Moon`
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Variant::lookup(value).ok_or_else(|| format!("unknown variant `{value}`"))
+    }
+}
+
+impl FromStr for VariantKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            _ => Err(format!(
+                "unknown variant kind `{value}`, expected dark or light"
+            )),
+        }
+    }
+}
+
+/// Palette of a built-in variant by its id, key or name
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Variant::from_str(value).map(|variant| variant.get_palette())
+    }
+}
+
+/// Parses a 6 digit hex color, the leading `#` is optional
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Rgb::from_hex(value.trim()).ok_or_else(|| format!("invalid hex color `{value}`"))
+    }
+}
+
+/// Parses comma or space separated components as output by the hsl formats, e.g. `hsl(2, 55%, 83%)` or `2 55% 83%`
+impl FromStr for Hsl {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        let components = trimmed
+            .strip_prefix("hsl(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .or_else(|| trimmed.strip_prefix('[')?.strip_suffix(']'))
+            .unwrap_or(trimmed)
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|component| !component.is_empty())
+            .map(|component| component.trim_end_matches('%').parse::<f32>())
+            .collect::<Result<Vec<_>, _>>();
+
+        match components.as_deref() {
+            Ok(&[h, s, l]) => Ok(Hsl::new(h, s, l)),
+            _ => Err(format!("invalid hsl color `{value}`")),
+        }
+    }
+}
+
+/// Error returned when a color string can't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    Empty,
+    /// Hex color without 3, 4, 6 or 8 hex digits
+    InvalidHex(String),
+    /// Word that isn't a css named color
+    UnknownName(String),
+    /// Function other than the css color functions, e.g. `color-mix`
+    UnknownFunction(String),
+    /// Function without a closing parenthesis
+    Unclosed(String),
+    /// Number of components of a color, alpha excluded
+    ComponentCount {
+        expected: usize,
+        found: usize,
+    },
+    /// Component that isn't a number, percentage, angle or `none`
+    InvalidComponent(String),
+    /// Xterm color index outside of the palette
+    InvalidIndex(String),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty color"),
+            Self::InvalidHex(value) => write!(f, "invalid hex color `{value}`"),
+            Self::UnknownName(name) => write!(f, "unknown color name `{name}`"),
+            Self::UnknownFunction(name) => write!(f, "unknown color function `{name}`"),
+            Self::Unclosed(value) => write!(f, "missing closing parenthesis in `{value}`"),
+            Self::ComponentCount { expected, found } => {
+                write!(f, "expected {expected} color components, found {found}")
+            }
+            Self::InvalidComponent(value) => write!(f, "invalid color component `{value}`"),
+            Self::InvalidIndex(value) => write!(f, "xterm color index `{value}` is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// How notations that look the same are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseColorOptions {
+    /// Whether alpha comes first in 4 and 8 digit hex colors, like the `ahex` format, instead of last like css
+    pub alpha_first: bool,
+    /// Palette of bare integers, which are read as xterm color indices
+    pub xterm: XtermPalette,
+}
+
+impl Default for ParseColorOptions {
+    fn default() -> Self {
+        Self {
+            alpha_first: false,
+            xterm: XtermPalette::Colors256,
+        }
+    }
+}
+
+impl Color {
+    /// Parses a color in any notation the build formats output, a css named color or a css color function,
+    /// e.g. `#eb6f92cc`, `235, 111, 146`, `hsl(343 76% 68% / 80%)`, `oklch(70% 0.156 4)` or `hotpink`.
+    /// Bare integers are xterm 256 color indices, alpha is kept as a percentage.
+    pub fn parse(value: &str) -> Result<Color, ParseColorError> {
+        Self::parse_with(value, ParseColorOptions::default())
+    }
+
+    pub fn parse_with(value: &str, options: ParseColorOptions) -> Result<Color, ParseColorError> {
+        let value = value.trim();
+        let lowercase = value.to_ascii_lowercase();
+
+        let (rgb, alpha) = if value.is_empty() {
+            return Err(ParseColorError::Empty);
+        } else if let Some(digits) = value.strip_prefix('#') {
+            parse_hex(value, digits, options.alpha_first)?
+        } else if let Some((name, rest)) = lowercase.split_once('(') {
+            let components = rest
+                .strip_suffix(')')
+                .ok_or_else(|| ParseColorError::Unclosed(value.to_string()))?;
+            parse_function(name.trim(), components)?
+        } else if value.len() >= 6 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+            parse_hex(value, value, options.alpha_first)?
+        } else if value.chars().all(|c| c.is_ascii_digit()) {
+            let index = value
+                .parse()
+                .ok()
+                .and_then(|index| options.xterm.get(index))
+                .ok_or_else(|| ParseColorError::InvalidIndex(value.to_string()))?;
+            (index, None)
+        } else if lowercase == "transparent" {
+            (Rgb::new(0, 0, 0), Some(0.0))
+        } else if lowercase
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || c == '-' || c == '_')
+        {
+            let rgb = Rgb::from_name(&lowercase)
+                .ok_or_else(|| ParseColorError::UnknownName(value.to_string()))?;
+            (rgb, None)
+        } else {
+            let components = lowercase
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .unwrap_or(&lowercase);
+            let is_hsl = split_components(components)
+                .iter()
+                .skip(1)
+                .any(|c| c.ends_with('%'));
+            parse_function(if is_hsl { "hsl" } else { "rgb" }, components)?
+        };
+
+        Ok(Color {
+            alpha,
+            ..Color::from(rgb)
+        })
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Color::parse(value)
+    }
+}
+
+fn parse_hex(
+    value: &str,
+    digits: &str,
+    alpha_first: bool,
+) -> Result<(Rgb, Option<f32>), ParseColorError> {
+    let invalid = || ParseColorError::InvalidHex(value.to_string());
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    // shorthand digits are doubled, e.g. `#f80` is `#ff8800`
+    let channels = match digits.len() {
+        3 | 4 => digits
+            .chars()
+            .map(|c| u8::from_str_radix(&c.to_string().repeat(2), 16))
+            .collect::<Result<Vec<_>, _>>(),
+        6 | 8 => (0..digits.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&digits[i..i + 2], 16))
+            .collect(),
+        _ => return Err(invalid()),
+    }
+    .map_err(|_| invalid())?;
+
+    let (alpha, channels) = match (channels.len(), alpha_first) {
+        (4, true) => (Some(channels[0]), &channels[1..]),
+        (4, false) => (Some(channels[3]), &channels[..3]),
+        _ => (None, &channels[..]),
+    };
+    let alpha = alpha.map(|a| f32::from(a) / 255.0 * 100.0);
+
+    Ok((Rgb::new(channels[0], channels[1], channels[2]), alpha))
+}
+
+/// Splits components on commas, semicolons or whitespace, in that order of preference
+fn split_components(value: &str) -> Vec<&str> {
+    let components = match [',', ';'].into_iter().find(|c| value.contains(*c)) {
+        Some(separator) => value.split(separator).map(str::trim).collect(),
+        None => value.split_whitespace().collect::<Vec<_>>(),
+    };
+
+    match components.as_slice() {
+        [""] => vec![],
+        _ => components,
+    }
+}
+
+/// A single color component, css `none` reads as zero
+enum Component {
+    Number(f32),
+    Percentage(f32),
+}
+
+impl Component {
+    fn parse(value: &str) -> Result<Self, ParseColorError> {
+        let number = |value: &str| match value.parse::<f32>() {
+            Ok(n) if n.is_finite() => Ok(n),
+            _ => Err(ParseColorError::InvalidComponent(value.to_string())),
+        };
+
+        match value.strip_suffix('%') {
+            _ if value == "none" => Ok(Self::Number(0.0)),
+            Some(percentage) => Ok(Self::Percentage(number(percentage)?)),
+            None => Ok(Self::Number(number(value)?)),
+        }
+    }
+
+    /// Value of the component where 100% equals `scale`
+    fn scaled(&self, scale: f32) -> f32 {
+        match self {
+            Self::Number(n) => *n,
+            Self::Percentage(p) => p / 100.0 * scale,
+        }
+    }
+}
+
+/// Parses a hue in degrees, optionally with a css angle unit
+fn parse_hue(value: &str) -> Result<f32, ParseColorError> {
+    let units = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / std::f32::consts::PI),
+        ("turn", 360.0),
+    ];
+    let (number, factor) = units
+        .into_iter()
+        .find_map(|(unit, factor)| Some((value.strip_suffix(unit)?, factor)))
+        .unwrap_or((value, 1.0));
+
+    match Component::parse(number)? {
+        Component::Number(n) => Ok(n * factor),
+        Component::Percentage(_) => Err(ParseColorError::InvalidComponent(value.to_string())),
+    }
+}
+
+/// Parses the arguments of a css color function, either legacy comma separated with an optional fourth alpha
+/// value or space separated with alpha after a `/`
+fn parse_function(name: &str, arguments: &str) -> Result<(Rgb, Option<f32>), ParseColorError> {
+    let (arguments, alpha) = match arguments.split_once('/') {
+        Some((arguments, alpha)) => (arguments, Some(alpha.trim())),
+        None => (arguments, None),
+    };
+    let mut components = split_components(arguments);
+    let alpha = match (alpha, components.len()) {
+        (Some(alpha), _) => Some(alpha),
+        (None, 4) => components.pop(),
+        (None, _) => None,
+    };
+    let [a, b, c] = components[..] else {
+        return Err(ParseColorError::ComponentCount {
+            expected: 3,
+            found: components.len(),
+        });
+    };
+    let component = Component::parse;
+
+    let rgb = match name {
+        "rgb" | "rgba" => {
+            let channel = |c| -> Result<u8, ParseColorError> {
+                Ok(component(c)?.scaled(255.0).clamp(0.0, 255.0).round() as u8)
+            };
+            Rgb::new(channel(a)?, channel(b)?, channel(c)?)
+        }
+        "hsl" | "hsla" => Hsl::new(
+            parse_hue(a)?,
+            component(b)?.scaled(100.0),
+            component(c)?.scaled(100.0),
+        )
+        .to_rgb(),
+        "hwb" => Hwb::new(
+            parse_hue(a)?,
+            component(b)?.scaled(100.0),
+            component(c)?.scaled(100.0),
+        )
+        .to_rgb(),
+        "lab" => Lab {
+            l: component(a)?.scaled(100.0),
+            a: component(b)?.scaled(125.0),
+            b: component(c)?.scaled(125.0),
+        }
+        .to_rgb(),
+        "lch" => Lch {
+            l: component(a)?.scaled(100.0),
+            c: component(b)?.scaled(150.0),
+            h: parse_hue(c)?,
+        }
+        .to_rgb(),
+        "oklab" => Oklab {
+            l: component(a)?.scaled(1.0),
+            a: component(b)?.scaled(0.4),
+            b: component(c)?.scaled(0.4),
+        }
+        .to_rgb(),
+        // clipped like the other spaces, mapping chroma into the gamut shifts colors close to its edge
+        "oklch" => Oklch {
+            l: component(a)?.scaled(1.0),
+            c: component(b)?.scaled(0.4),
+            h: parse_hue(c)?,
+        }
+        .to_oklab()
+        .to_rgb(),
+        _ => return Err(ParseColorError::UnknownFunction(name.to_string())),
+    };
+
+    let alpha = match alpha {
+        Some(alpha) => Some((component(alpha)?.scaled(1.0) * 100.0).clamp(0.0, 100.0)),
+        None => None,
+    };
+
+    Ok((rgb, alpha))
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for VariantKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[derive(Deserialize)]
+struct RgbFields {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+impl From<RgbFields> for Rgb {
+    fn from(value: RgbFields) -> Self {
+        Rgb::new(value.r, value.g, value.b)
+    }
+}
+
+#[derive(Deserialize)]
+struct HslFields {
+    h: f32,
+    s: f32,
+    l: f32,
+}
+
+impl From<HslFields> for Hsl {
+    fn from(value: HslFields) -> Self {
+        Hsl::new(value.h, value.s, value.l)
+    }
+}
+
+/// Other representations of a serialized color are derived from its rgb value
+#[derive(Deserialize)]
+struct ColorFields {
+    rgb: Rgb,
+    alpha: Option<f32>,
+}
+
+impl From<ColorFields> for Color {
+    fn from(value: ColorFields) -> Self {
+        Color {
+            alpha: value.alpha,
+            ..Color::from(value.rgb)
+        }
+    }
+}
+
+/// Reads colors from a string or from their serialized struct form
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        string_or_fields::<_, _, RgbFields>(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Hsl {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        string_or_fields::<_, _, HslFields>(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        string_or_fields::<_, _, ColorFields>(deserializer)
+    }
+}
+
+fn string_or_fields<'de, D, T, F>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+    F: Deserialize<'de> + Into<T>,
+{
+    struct StringOrFields<T, F>(PhantomData<(T, F)>);
+
+    impl<'de, T, F> Visitor<'de> for StringOrFields<T, F>
+    where
+        T: FromStr,
+        T::Err: Display,
+        F: Deserialize<'de> + Into<T>,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a color string or map")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+            value.parse().map_err(E::custom)
+        }
+
+        fn visit_map<M: MapAccess<'de>>(self, map: M) -> Result<T, M::Error> {
+            F::deserialize(de::value::MapAccessDeserializer::new(map)).map(Into::into)
+        }
+    }
+
+    deserializer.deserialize_any(StringOrFields::<T, F>(PhantomData))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Role;
+
+    #[test]
+    fn variants() {
+        for value in [
+            "moon",
+            "MOON",
+            "rose-pine-moon",
+            "Rosé Pine Moon",
+            "ROSÉ PINE MOON",
+        ] {
+            assert_eq!(value.parse(), Ok(Variant::Moon));
+        }
+        assert_eq!("Rose-Pine".parse(), Ok(Variant::Main));
+        assert!("rose-pine-noon".parse::<Variant>().is_err());
+        assert_eq!("Light".parse(), Ok(VariantKind::Light));
+
+        let palette: Palette = "dawn".parse().unwrap();
+        assert_eq!(
+            palette.get_rgb(&Role::Love),
+            Variant::Dawn.get_rgb(Role::Love)
+        );
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!("#eb6f92".parse(), Ok(Rgb::new(235, 111, 146)));
+        assert!("#eb6f9".parse::<Rgb>().is_err());
+        assert_eq!(
+            "hsl(343, 76%, 68%)".parse(),
+            Ok(Hsl::new(343.0, 76.0, 68.0))
+        );
+        assert_eq!("343 76% 68%".parse(), Ok(Hsl::new(343.0, 76.0, 68.0)));
+        assert!("343, 76%".parse::<Hsl>().is_err());
+    }
+
+    fn assert_parse(value: &str, hex: &str, alpha: Option<f32>) {
+        let color = Color::parse(value).unwrap();
+        assert_eq!(color.hex, hex, "{value}");
+        match (color.alpha, alpha) {
+            (Some(a), Some(b)) => assert!((a - b).abs() < 0.5, "{value}: {a} != {b}"),
+            (a, b) => assert_eq!(a, b, "{value}"),
+        }
+    }
+
+    #[test]
+    fn parse_notations() {
+        assert_parse("#eb6f92", "eb6f92", None);
+        assert_parse("EB6F92CC", "eb6f92", Some(80.0));
+        assert_parse("#f80", "ff8800", None);
+        assert_parse("#f808", "ff8800", Some(53.3));
+        assert_parse("235, 111, 146", "eb6f92", None);
+        assert_parse("235 111 146 0.8", "eb6f92", Some(80.0));
+        assert_parse("235;111;146", "eb6f92", None);
+        assert_parse("[235, 111, 146]", "eb6f92", None);
+        assert_parse("rgba(235, 111, 146, 0.5)", "eb6f92", Some(50.0));
+        assert_parse("rgb(100% 0% 50% / 25%)", "ff0080", Some(25.0));
+        assert_parse("343, 76%, 68%", "eb6f93", None);
+        assert_parse("hsl(343.06 75.61% 67.84%)", "eb6f92", None);
+        assert_parse("hsl(0.953turn 76% 68%)", "eb6f92", None);
+        assert_parse("hwb(120 0% 50%)", "008000", None);
+        assert_parse("lab(54.29% 80.8 69.89)", "ff0000", None);
+        assert_parse("lch(54.29 106.84 40.85deg / 0.5)", "ff0000", Some(50.0));
+        assert_parse("oklab(62.8% 0.22486 0.12585)", "ff0000", None);
+        assert_parse("oklch(0.628 0.2577 29.23)", "ff0000", None);
+        assert_parse("oklch(none none none)", "000000", None);
+        assert_parse("RebeccaPurple", "663399", None);
+        assert_parse("transparent", "000000", Some(0.0));
+        assert_parse("196", "ff0000", None);
+
+        let options = ParseColorOptions {
+            alpha_first: true,
+            xterm: XtermPalette::Colors88,
+        };
+        let color = Color::parse_with("#cceb6f92", options).unwrap();
+        assert_eq!(color.hex, "eb6f92");
+        assert_eq!(Color::parse_with("9", options).unwrap().hex, "ff0000");
+        assert_eq!(Color::parse_with("80", options).unwrap().hex, "2e2e2e");
+    }
+
+    #[test]
+    fn parse_errors() {
+        use ParseColorError::*;
+
+        let err = |value: &str| Color::parse(value).unwrap_err();
+        assert_eq!(err("  "), Empty);
+        assert_eq!(err("#eb6f9"), InvalidHex("#eb6f9".into()));
+        assert_eq!(err("#eb6f9g"), InvalidHex("#eb6f9g".into()));
+        assert_eq!(err("rose-pine"), UnknownName("rose-pine".into()));
+        assert_eq!(
+            err("color-mix(in oklab, red, blue)"),
+            UnknownFunction("color-mix".into())
+        );
+        assert_eq!(err("rgb(1, 2, 3"), Unclosed("rgb(1, 2, 3".into()));
+        assert_eq!(
+            err("rgb(1 2)"),
+            ComponentCount {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            err("hsl(1 2 3 4 5)"),
+            ComponentCount {
+                expected: 3,
+                found: 5
+            }
+        );
+        assert_eq!(err("rgb(1 2 blue)"), InvalidComponent("blue".into()));
+        assert_eq!(err("hsl(10% 20% 30%)"), InvalidComponent("10%".into()));
+        assert_eq!(err("256"), InvalidIndex("256".into()));
+        assert_eq!(
+            err("256").to_string(),
+            "xterm color index `256` is out of range"
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let love = Role::Love.get_color(&Variant::Moon);
+        let json = serde_json::to_string(&love).unwrap();
+        let parsed: Color = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.rgb, love.rgb);
+        assert_eq!(parsed.hsl, love.hsl);
+
+        let parsed: Color = serde_json::from_str("\"eb6f92\"").unwrap();
+        assert_eq!(parsed.rgb, love.rgb);
+        let parsed: Color = serde_json::from_str("\"rgb(235 111 146 / 40%)\"").unwrap();
+        assert_eq!(parsed.alpha, Some(40.0));
+        assert_eq!(parsed.rgb, love.rgb);
+        let parsed: Hsl = serde_json::from_str(r#"{ "h": 343, "s": 76, "l": 68 }"#).unwrap();
+        assert_eq!(parsed, Hsl::new(343.0, 76.0, 68.0));
+        let err = serde_json::from_str::<Rgb>(r#"{ "r": 300, "g": 0, "b": 0 }"#).unwrap_err();
+        assert!(err.to_string().contains("300"));
+
+        let variants: Vec<Variant> = serde_json::from_str(r#"["main", "Rosé Pine Dawn"]"#).unwrap();
+        assert_eq!(variants, [Variant::Main, Variant::Dawn]);
+        assert_eq!(
+            serde_json::from_str::<VariantKind>("\"DARK\"").unwrap(),
+            VariantKind::Dark
+        );
+    }
+}
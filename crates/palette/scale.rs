@@ -0,0 +1,129 @@
+use crate::{Color, Oklch, Rgb, Role, Variant, VariantDefinition};
+use std::{cmp::Ordering, collections::BTreeMap};
+
+/// Steps of a tonal scale from lightest to darkest, following tailwind's naming
+pub const SHADES: [u16; 11] = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950];
+
+/// Oklch lightness of each step in [`SHADES`] before anchoring
+const LIGHTNESS: [f32; 11] = [
+    0.971, 0.936, 0.885, 0.808, 0.704, 0.637, 0.577, 0.505, 0.444, 0.396, 0.258,
+];
+
+impl Rgb {
+    /// Step of [`SHADES`] whose lightness is closest to this color
+    pub fn nearest_shade(self) -> u16 {
+        let l = self.to_oklch().l;
+        let index = (0..SHADES.len())
+            .min_by(|a, b| {
+                (LIGHTNESS[*a] - l)
+                    .abs()
+                    .total_cmp(&(LIGHTNESS[*b] - l).abs())
+            })
+            .expect("there is at least one shade");
+
+        SHADES[index]
+    }
+
+    /// Tonal scale keeping the hue and chroma of this color, with this exact color at the `anchor` step.
+    /// Lightness is spread so the lightest and darkest steps stay fixed while the anchor moves
+    /// to the lightness of this color, chroma is only reduced where a shade falls outside of srgb.
+    /// Returns `None` if `anchor` isn't one of [`SHADES`].
+    pub fn tonal_scale(self, anchor: u16) -> Option<[Rgb; 11]> {
+        let anchor = SHADES.iter().position(|step| *step == anchor)?;
+        let color = self.to_oklch();
+        let (first, last) = (LIGHTNESS[0], LIGHTNESS[SHADES.len() - 1]);
+        let target = LIGHTNESS[anchor];
+
+        let lightness = |i: usize| match i.cmp(&anchor) {
+            Ordering::Less => {
+                color.l + (LIGHTNESS[i] - target) / (first - target) * (first - color.l)
+            }
+            Ordering::Greater => {
+                color.l + (LIGHTNESS[i] - target) / (last - target) * (last - color.l)
+            }
+            Ordering::Equal => color.l,
+        };
+
+        Some(std::array::from_fn(|i| match i == anchor {
+            true => self,
+            false => Oklch {
+                l: lightness(i),
+                ..color
+            }
+            .to_rgb(),
+        }))
+    }
+
+    /// Shade at `step` of the tonal scale anchored at the [nearest shade](Rgb::nearest_shade)
+    pub fn shade(self, step: u16) -> Option<Rgb> {
+        let index = SHADES.iter().position(|s| *s == step)?;
+        self.tonal_scale(self.nearest_shade())
+            .map(|scale| scale[index])
+    }
+}
+
+impl Color {
+    pub fn shade(&self, step: u16) -> Option<Color> {
+        self.rgb.shade(step).map(Color::from)
+    }
+
+    /// Every shade of the tonal scale keyed by step
+    pub fn shades(&self) -> BTreeMap<u16, Color> {
+        let scale = self
+            .rgb
+            .tonal_scale(self.rgb.nearest_shade())
+            .expect("nearest shade is a valid anchor");
+
+        SHADES.into_iter().zip(scale.map(Color::from)).collect()
+    }
+}
+
+impl Variant {
+    pub fn shade(&self, role: Role, step: u16) -> Option<Rgb> {
+        self.get_rgb(role).shade(step)
+    }
+}
+
+impl VariantDefinition {
+    pub fn shade(&self, role: Role, step: u16) -> Option<Rgb> {
+        self.get_rgb(role).shade(step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn anchored() {
+        for variant in Variant::iter() {
+            for role in Role::iter() {
+                let rgb = variant.get_rgb(role);
+                for (i, anchor) in SHADES.into_iter().enumerate() {
+                    assert_eq!(rgb.tonal_scale(anchor).unwrap()[i], rgb);
+                }
+                assert_eq!(variant.shade(role, rgb.nearest_shade()), Some(rgb));
+            }
+        }
+
+        assert!(Rgb::new(0, 0, 0).tonal_scale(250).is_none());
+        assert!(Variant::Main.shade(Role::Love, 250).is_none());
+    }
+
+    #[test]
+    fn monotonic_lightness() {
+        let love = Variant::Main.get_rgb(Role::Love);
+        assert_eq!(love.nearest_shade(), 400);
+
+        let scale = love.tonal_scale(400).unwrap();
+        for pair in scale.windows(2) {
+            assert!(pair[0].to_oklch().l > pair[1].to_oklch().l);
+        }
+
+        // hue is kept where chroma allows it
+        let hue = love.to_oklch().h;
+        assert!((scale[6].to_oklch().h - hue).abs() < 2.0);
+        assert!((scale[2].to_oklch().h - hue).abs() < 2.0);
+    }
+}
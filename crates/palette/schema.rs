@@ -0,0 +1,107 @@
+use crate::{PaletteFile, Role, VariantKind};
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+
+/// Schema generated by [`PaletteFile::json_schema`], checked in for external tooling
+pub const SCHEMA: &str = include_str!("palette.schema.json");
+
+const HEX_PATTERN: &str = "^#?[0-9a-fA-F]{6}$";
+const ROLE_NAME_PATTERN: &str = "^[a-z][a-z0-9_]*$";
+
+impl PaletteFile {
+    /// Json schema of palette files in their toml or json form
+    pub fn json_schema() -> Value {
+        let roles = Role::iter()
+            .map(|role| role.to_string())
+            .collect::<Vec<_>>();
+        let hex = json!({ "type": "string", "pattern": HEX_PATTERN });
+
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Rosé Pine palette",
+            "type": "object",
+            "required": ["variants"],
+            "properties": {
+                "variants": { "type": "array", "items": { "$ref": "#/$defs/variant" } }
+            },
+            "$defs": {
+                "variant": {
+                    "type": "object",
+                    "required": ["id", "name", "key", "kind", "colors"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "name": { "type": "string" },
+                        "key": { "type": "string" },
+                        "kind": {
+                            "enum": [VariantKind::Dark.to_string(), VariantKind::Light.to_string()]
+                        },
+                        "description": { "type": "string" },
+                        "slot": {
+                            "description": "Built-in variant used for role groups, its id, key or name",
+                            "type": "string"
+                        },
+                        "colors": {
+                            "type": "object",
+                            "required": roles,
+                            "properties": roles
+                                .iter()
+                                .map(|role| (role.clone(), json!({ "$ref": "#/$defs/hex" })))
+                                .collect::<serde_json::Map<_, _>>(),
+                            "additionalProperties": false
+                        },
+                        "roles": {
+                            "description": "Additional named colors",
+                            "type": "object",
+                            "propertyNames": { "pattern": ROLE_NAME_PATTERN },
+                            "additionalProperties": { "$ref": "#/$defs/hex" }
+                        },
+                        "aliases": {
+                            "description": "Semantic names for roles, optionally at an alpha percentage",
+                            "type": "object",
+                            "propertyNames": { "pattern": ROLE_NAME_PATTERN },
+                            "additionalProperties": {
+                                "type": "string",
+                                "pattern": format!("^({})(/(100|[1-9]?[0-9]))?$", roles.join("|"))
+                            }
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "hex": hex
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_in_schema_is_current() {
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+        assert_eq!(
+            schema,
+            PaletteFile::json_schema(),
+            "palette.schema.json is outdated, regenerate it with `rose-pine-build schema`"
+        );
+    }
+
+    #[test]
+    fn covers_serialized_fields() {
+        let schema = PaletteFile::json_schema();
+        let properties = &schema["$defs"]["variant"]["properties"];
+
+        let serialized = serde_json::to_value(PaletteFile::builtin()).unwrap();
+        for variant in serialized["variants"].as_array().unwrap() {
+            for (key, value) in variant.as_object().unwrap() {
+                assert!(properties.get(key).is_some(), "missing field `{key}`");
+                if key == "colors" {
+                    for role in value.as_object().unwrap().keys() {
+                        assert!(properties["colors"]["properties"].get(role).is_some());
+                    }
+                }
+            }
+        }
+    }
+}
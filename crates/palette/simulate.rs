@@ -0,0 +1,122 @@
+use crate::{Palette, Rgb, VariantDefinition};
+use heck::ToTitleCase;
+use serde::Serialize;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Color vision deficiency that can be simulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Deficiency {
+    /// No functioning long-wavelength (red) cones
+    Protanopia,
+    /// No functioning medium-wavelength (green) cones
+    Deuteranopia,
+    /// No functioning short-wavelength (blue) cones
+    Tritanopia,
+    /// Complete absence of color vision
+    Achromatopsia,
+}
+
+impl Deficiency {
+    /// Transform in linear srgb, dichromacies follow Machado et al. (2009) at full severity
+    const fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Self::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Self::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+            // only luminance is perceived
+            Self::Achromatopsia => [[0.2126, 0.7152, 0.0722]; 3],
+        }
+    }
+}
+
+impl Rgb {
+    /// How this color is perceived with a color vision deficiency
+    pub fn simulate(self, deficiency: Deficiency) -> Rgb {
+        let linear = self.to_linear();
+        let channels = deficiency
+            .matrix()
+            .map(|row| row.iter().zip(linear).map(|(m, c)| m * c).sum());
+
+        Rgb::from_linear(channels)
+    }
+}
+
+impl VariantDefinition {
+    /// Copy of this variant as perceived with a color vision deficiency,
+    /// the deficiency is appended to its id and key, e.g. `rose-pine-moon-deuteranopia`
+    pub fn simulate(&self, deficiency: Deficiency) -> VariantDefinition {
+        Self {
+            id: format!("{}-{deficiency}", self.id),
+            name: format!("{} ({})", self.name, deficiency.to_string().to_title_case()),
+            key: format!("{}-{deficiency}", self.key),
+            kind: self.kind,
+            description: self.description.clone(),
+            slot: Some(self.slot()),
+            colors: Palette::from_fn(|role| self.get_rgb(role).simulate(deficiency)),
+            roles: self
+                .roles
+                .iter()
+                .map(|(name, rgb)| (name.clone(), rgb.simulate(deficiency)))
+                .collect(),
+            aliases: self.aliases.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Role, Variant};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn neutral_colors() {
+        for deficiency in Deficiency::iter() {
+            for rgb in [Rgb::new(0, 0, 0), Rgb::new(255, 255, 255)] {
+                assert_eq!(rgb.simulate(deficiency), rgb);
+            }
+        }
+    }
+
+    #[test]
+    fn simulate() {
+        let red = Rgb::new(255, 0, 0);
+        assert_eq!(
+            red.simulate(Deficiency::Deuteranopia),
+            Rgb::new(163, 144, 0)
+        );
+        assert_eq!(
+            red.simulate(Deficiency::Achromatopsia),
+            Rgb::new(127, 127, 127)
+        );
+    }
+
+    #[test]
+    fn simulated_variant() {
+        let moon = VariantDefinition::from(Variant::Moon).simulate(Deficiency::Deuteranopia);
+        assert_eq!(moon.id, "rose-pine-moon-deuteranopia");
+        assert_eq!(moon.key, "moon-deuteranopia");
+        assert_eq!(moon.name, "Rosé Pine Moon (Deuteranopia)");
+        assert_eq!(moon.slot(), Variant::Moon);
+        assert_eq!(
+            moon.get_rgb(Role::Love),
+            Variant::Moon
+                .get_rgb(Role::Love)
+                .simulate(Deficiency::Deuteranopia)
+        );
+    }
+}
@@ -0,0 +1,381 @@
+use crate::{Hsl, Hwb, Rgb};
+use serde::Serialize;
+
+/// CIE XYZ tristimulus values relative to the D65 white point, `y` of white is `1.0`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Xyz {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// CIELAB relative to the D50 white point like css `lab()`, lightness ranges from `0.0` to `100.0`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Lab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+/// Cylindrical form of [`Lab`], hue in degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Lch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+/// Oklab, lightness ranges from `0.0` to `1.0`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+/// Cylindrical form of [`Oklab`], hue in degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+/// D50 reference white used by css `lab()` and `lch()`
+const D50: Xyz = Xyz {
+    x: 0.964_295_7,
+    y: 1.0,
+    z: 0.825_104_6,
+};
+
+impl Rgb {
+    /// Channels with the srgb transfer function removed
+    pub fn to_linear(self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|c| {
+            let c = f32::from(c) / 255.0;
+            if c <= 0.040_45 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    }
+
+    pub fn to_xyz(self) -> Xyz {
+        let [r, g, b] = self.to_linear();
+        Xyz {
+            x: 0.412_390_8 * r + 0.357_584_33 * g + 0.180_480_8 * b,
+            y: 0.212_639 * r + 0.715_168_7 * g + 0.072_192_32 * b,
+            z: 0.019_330_82 * r + 0.119_194_78 * g + 0.950_532_2 * b,
+        }
+    }
+
+    pub fn to_lab(self) -> Lab {
+        let Xyz { x, y, z } = self.to_xyz();
+
+        // bradford chromatic adaptation from D65 to D50
+        let d50 = Xyz {
+            x: 1.047_93 * x + 0.022_946_8 * y - 0.050_192_2 * z,
+            y: 0.029_627_8 * x + 0.990_434_5 * y - 0.017_073_8 * z,
+            z: -0.009_243_1 * x + 0.015_055_1 * y + 0.751_874_3 * z,
+        };
+
+        let f = |t: f32| {
+            const EPSILON: f32 = 216.0 / 24389.0;
+            const KAPPA: f32 = 24389.0 / 27.0;
+            if t > EPSILON {
+                t.cbrt()
+            } else {
+                (KAPPA * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(d50.x / D50.x), f(d50.y / D50.y), f(d50.z / D50.z));
+
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+
+    pub fn to_lch(self) -> Lch {
+        let Lab { l, a, b } = self.to_lab();
+        let (c, h) = polar(a, b);
+        Lch { l, c, h }
+    }
+
+    pub fn to_oklab(self) -> Oklab {
+        let [r, g, b] = self.to_linear();
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+        Oklab {
+            l: 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a: 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b: 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        }
+    }
+
+    pub fn to_oklch(self) -> Oklch {
+        let Oklab { l, a, b } = self.to_oklab();
+        let (c, h) = polar(a, b);
+        Oklch { l, c, h }
+    }
+}
+
+impl Rgb {
+    /// Applies the srgb transfer function to linear channels, clamping them to the gamut
+    pub fn from_linear(channels: [f32; 3]) -> Rgb {
+        let [r, g, b] = channels.map(|c| {
+            let c = if c <= 0.003_130_8 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (c.clamp(0.0, 1.0) * 255.0).round() as u8
+        });
+
+        Rgb::new(r, g, b)
+    }
+}
+
+impl Hsl {
+    pub fn to_rgb(self) -> Rgb {
+        let [r, g, b] = self.unit_channels();
+        unit_rgb(r, g, b)
+    }
+
+    /// Rgb channels in `0.0..=1.0`
+    fn unit_channels(self) -> [f32; 3] {
+        let (s, l) = (
+            self.s.clamp(0.0, 100.0) / 100.0,
+            self.l.clamp(0.0, 100.0) / 100.0,
+        );
+        let a = s * l.min(1.0 - l);
+
+        [0.0, 8.0, 4.0].map(|n: f32| {
+            let k = (n + self.h.rem_euclid(360.0) / 30.0) % 12.0;
+            l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        })
+    }
+}
+
+impl Hwb {
+    pub fn to_rgb(self) -> Rgb {
+        let (w, b) = (
+            self.w.clamp(0.0, 100.0) / 100.0,
+            self.b.clamp(0.0, 100.0) / 100.0,
+        );
+        if w + b >= 1.0 {
+            let gray = w / (w + b);
+            return unit_rgb(gray, gray, gray);
+        }
+
+        let [r, g, blue] = Hsl::new(self.h, 100.0, 50.0)
+            .unit_channels()
+            .map(|c| c * (1.0 - w - b) + w);
+        unit_rgb(r, g, blue)
+    }
+}
+
+/// Rounds channels in `0.0..=1.0` to an rgb color
+fn unit_rgb(r: f32, g: f32, b: f32) -> Rgb {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Rgb::new(channel(r), channel(g), channel(b))
+}
+
+impl Xyz {
+    /// Converts to rgb, clamping colors outside of the srgb gamut
+    pub fn to_rgb(self) -> Rgb {
+        let Xyz { x, y, z } = self;
+        Rgb::from_linear([
+            3.240_97 * x - 1.537_383_2 * y - 0.498_610_76 * z,
+            -0.969_243_6 * x + 1.875_967_5 * y + 0.041_555_06 * z,
+            0.055_630_08 * x - 0.203_976_96 * y + 1.056_971_5 * z,
+        ])
+    }
+}
+
+impl Lab {
+    pub fn to_xyz(self) -> Xyz {
+        const EPSILON: f32 = 216.0 / 24389.0;
+        const KAPPA: f32 = 24389.0 / 27.0;
+        let fy = (self.l + 16.0) / 116.0;
+        let fx = self.a / 500.0 + fy;
+        let fz = fy - self.b / 200.0;
+        let f = |t: f32| match t.powi(3) {
+            cube if cube > EPSILON => cube,
+            _ => (116.0 * t - 16.0) / KAPPA,
+        };
+        let y = match self.l > KAPPA * EPSILON {
+            true => fy.powi(3),
+            false => self.l / KAPPA,
+        };
+        let d50 = Xyz {
+            x: f(fx) * D50.x,
+            y: y * D50.y,
+            z: f(fz) * D50.z,
+        };
+
+        // bradford chromatic adaptation from D50 back to D65
+        Xyz {
+            x: 0.955_473_4 * d50.x - 0.023_098_455 * d50.y + 0.063_259_24 * d50.z,
+            y: -0.028_369_71 * d50.x + 1.009_995_4 * d50.y + 0.021_041_44 * d50.z,
+            z: 0.012_314_015 * d50.x - 0.020_507_65 * d50.y + 1.330_365_9 * d50.z,
+        }
+    }
+
+    pub fn to_rgb(self) -> Rgb {
+        self.to_xyz().to_rgb()
+    }
+}
+
+impl Lch {
+    pub fn to_lab(self) -> Lab {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+        Lab {
+            l: self.l,
+            a: self.c * cos,
+            b: self.c * sin,
+        }
+    }
+
+    pub fn to_rgb(self) -> Rgb {
+        self.to_lab().to_rgb()
+    }
+}
+
+impl Oklab {
+    /// Channels in linear srgb, which may be outside of `0.0..=1.0` for colors outside the gamut
+    fn to_linear(self) -> [f32; 3] {
+        let l = (self.l + 0.396_337_78 * self.a + 0.215_803_76 * self.b).powi(3);
+        let m = (self.l - 0.105_561_346 * self.a - 0.063_854_17 * self.b).powi(3);
+        let s = (self.l - 0.089_484_18 * self.a - 1.291_485_5 * self.b).powi(3);
+
+        [
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        ]
+    }
+
+    pub fn to_rgb(self) -> Rgb {
+        Rgb::from_linear(self.to_linear())
+    }
+}
+
+impl Oklch {
+    pub fn to_oklab(self) -> Oklab {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+        Oklab {
+            l: self.l,
+            a: self.c * cos,
+            b: self.c * sin,
+        }
+    }
+
+    /// Converts to rgb, reducing chroma until the color fits in the srgb gamut so hue is preserved
+    pub fn to_rgb(self) -> Rgb {
+        // there is no chroma left at the extremes of lightness
+        if self.l <= 0.0 || self.l >= 1.0 {
+            return Oklch { c: 0.0, ..self }.to_oklab().to_rgb();
+        }
+
+        let in_gamut = |c: f32| {
+            Oklch { c, ..self }
+                .to_oklab()
+                .to_linear()
+                .iter()
+                .all(|c| (-1e-5..=1.000_01).contains(c))
+        };
+
+        if in_gamut(self.c) {
+            return self.to_oklab().to_rgb();
+        }
+
+        let (mut low, mut high) = (0.0, self.c);
+        for _ in 0..16 {
+            let mid = (low + high) / 2.0;
+            match in_gamut(mid) {
+                true => low = mid,
+                false => high = mid,
+            }
+        }
+
+        Oklch { c: low, ..self }.to_oklab().to_rgb()
+    }
+}
+
+/// Converts rectangular `a` and `b` coordinates into chroma and a hue in degrees
+fn polar(a: f32, b: f32) -> (f32, f32) {
+    let c = a.hypot(b);
+    // hue is meaningless for achromatic colors, snap it to zero instead of float noise
+    let h = if c < 1e-4 {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    };
+
+    (c, h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32, tolerance: f32) {
+        assert!((a - b).abs() < tolerance, "{a} != {b}");
+    }
+
+    #[test]
+    fn white() {
+        let white = Rgb::new(255, 255, 255);
+        assert_close(white.to_xyz().y, 1.0, 1e-4);
+        assert_close(white.to_lab().l, 100.0, 1e-2);
+        assert_close(white.to_lch().c, 0.0, 1e-2);
+        assert_close(white.to_oklab().l, 1.0, 1e-4);
+        assert_close(white.to_oklch().c, 0.0, 1e-4);
+    }
+
+    #[test]
+    fn oklab_round_trip() {
+        for rgb in [
+            Rgb::new(235, 111, 146),
+            Rgb::new(25, 23, 36),
+            Rgb::new(255, 0, 0),
+        ] {
+            assert_eq!(rgb.to_oklab().to_rgb(), rgb);
+            assert_eq!(rgb.to_oklch().to_rgb(), rgb);
+        }
+    }
+
+    #[test]
+    fn lab_round_trip() {
+        for rgb in [
+            Rgb::new(235, 111, 146),
+            Rgb::new(25, 23, 36),
+            Rgb::new(255, 255, 255),
+            Rgb::new(0, 0, 255),
+        ] {
+            assert_eq!(rgb.to_lab().to_rgb(), rgb);
+            assert_eq!(rgb.to_lch().to_rgb(), rgb);
+            assert_eq!(rgb.to_hsl().to_rgb(), rgb);
+            assert_eq!(rgb.to_hwb().to_rgb(), rgb);
+        }
+    }
+
+    #[test]
+    fn red() {
+        let red = Rgb::new(255, 0, 0);
+        let lab = red.to_lab();
+        assert_close(lab.l, 54.29, 0.05);
+        assert_close(lab.a, 80.80, 0.05);
+        assert_close(lab.b, 69.89, 0.05);
+
+        let oklch = red.to_oklch();
+        assert_close(oklch.l, 0.628, 1e-3);
+        assert_close(oklch.c, 0.2577, 1e-3);
+        assert_close(oklch.h, 29.23, 0.05);
+    }
+}
@@ -1,97 +1,177 @@
-use crate::{Color, Hsl, Metadata, Rgb, Role};
-use heck::ToSnakeCase;
+#[cfg(feature = "std")]
+use crate::{Color, VariantDefinition};
+use crate::{Hsl, Rgb, Role};
+use serde::Serialize;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter};
 
-#[derive(Debug, Clone, Copy, Display, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, Serialize)]
 #[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum Variant {
     Main,
     Moon,
     Dawn,
 }
 
-#[derive(Debug, Copy, Clone, Display)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Display, Serialize)]
 #[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum VariantKind {
     Light,
     Dark,
 }
 
-type Colors = (Rgb, Hsl);
+/// Colors for every role, hsl and other representations are derived from the rgb values
+#[derive(Debug, Clone, Copy)]
 pub struct Palette {
-    base: Colors,
-    surface: Colors,
-    overlay: Colors,
-    muted: Colors,
-    subtle: Colors,
-    text: Colors,
-    love: Colors,
-    gold: Colors,
-    rose: Colors,
-    pine: Colors,
-    foam: Colors,
-    iris: Colors,
-    highlight_low: Colors,
-    highlight_med: Colors,
-    highlight_high: Colors,
+    base: Rgb,
+    surface: Rgb,
+    overlay: Rgb,
+    muted: Rgb,
+    subtle: Rgb,
+    text: Rgb,
+    love: Rgb,
+    gold: Rgb,
+    rose: Rgb,
+    pine: Rgb,
+    foam: Rgb,
+    iris: Rgb,
+    highlight_low: Rgb,
+    highlight_med: Rgb,
+    highlight_high: Rgb,
 }
 
 impl Palette {
     pub const MAIN: Palette = Palette {
-        base: (rgb(25, 23, 36), hsl(249, 22, 12)),
-        surface: (rgb(31, 29, 46), hsl(247, 23, 15)),
-        overlay: (rgb(38, 35, 58), hsl(248, 25, 18)),
-        muted: (rgb(110, 106, 134), hsl(249, 12, 47)),
-        subtle: (rgb(144, 140, 170), hsl(248, 15, 61)),
-        text: (rgb(224, 222, 244), hsl(245, 50, 91)),
-        love: (rgb(235, 111, 146), hsl(343, 76, 68)),
-        gold: (rgb(246, 193, 119), hsl(35, 88, 72)),
-        rose: (rgb(235, 188, 186), hsl(2, 55, 83)),
-        pine: (rgb(49, 116, 143), hsl(197, 49, 38)),
-        foam: (rgb(156, 207, 216), hsl(189, 43, 73)),
-        iris: (rgb(196, 167, 231), hsl(267, 57, 78)),
-        highlight_low: (rgb(33, 32, 46), hsl(244, 18, 15)),
-        highlight_med: (rgb(64, 61, 82), hsl(249, 15, 28)),
-        highlight_high: (rgb(82, 79, 103), hsl(248, 13, 36)),
+        base: rgb(25, 23, 36),
+        surface: rgb(31, 29, 46),
+        overlay: rgb(38, 35, 58),
+        muted: rgb(110, 106, 134),
+        subtle: rgb(144, 140, 170),
+        text: rgb(224, 222, 244),
+        love: rgb(235, 111, 146),
+        gold: rgb(246, 193, 119),
+        rose: rgb(235, 188, 186),
+        pine: rgb(49, 116, 143),
+        foam: rgb(156, 207, 216),
+        iris: rgb(196, 167, 231),
+        highlight_low: rgb(33, 32, 46),
+        highlight_med: rgb(64, 61, 82),
+        highlight_high: rgb(82, 79, 103),
     };
     pub const MOON: Palette = Palette {
-        base: (rgb(35, 33, 54), hsl(246, 24, 17)),
-        surface: (rgb(42, 39, 63), hsl(248, 24, 20)),
-        overlay: (rgb(57, 53, 82), hsl(248, 21, 26)),
-        muted: (rgb(110, 106, 134), hsl(249, 12, 47)),
-        subtle: (rgb(144, 140, 170), hsl(248, 15, 61)),
-        text: (rgb(224, 222, 244), hsl(245, 50, 91)),
-        love: (rgb(235, 111, 146), hsl(343, 76, 68)),
-        gold: (rgb(246, 193, 119), hsl(35, 88, 72)),
-        rose: (rgb(234, 154, 151), hsl(2, 66, 75)),
-        pine: (rgb(62, 143, 176), hsl(197, 48, 47)),
-        foam: (rgb(156, 207, 216), hsl(189, 43, 73)),
-        iris: (rgb(196, 167, 231), hsl(267, 57, 78)),
-        highlight_low: (rgb(42, 40, 62), hsl(245, 22, 20)),
-        highlight_med: (rgb(68, 65, 90), hsl(247, 16, 30)),
-        highlight_high: (rgb(86, 82, 110), hsl(249, 15, 38)),
+        base: rgb(35, 33, 54),
+        surface: rgb(42, 39, 63),
+        overlay: rgb(57, 53, 82),
+        muted: rgb(110, 106, 134),
+        subtle: rgb(144, 140, 170),
+        text: rgb(224, 222, 244),
+        love: rgb(235, 111, 146),
+        gold: rgb(246, 193, 119),
+        rose: rgb(234, 154, 151),
+        pine: rgb(62, 143, 176),
+        foam: rgb(156, 207, 216),
+        iris: rgb(196, 167, 231),
+        highlight_low: rgb(42, 40, 62),
+        highlight_med: rgb(68, 65, 90),
+        highlight_high: rgb(86, 82, 110),
     };
     pub const DAWN: Palette = Palette {
-        base: (rgb(250, 244, 237), hsl(32, 57, 95)),
-        surface: (rgb(255, 250, 243), hsl(35, 100, 98)),
-        overlay: (rgb(242, 233, 222), hsl(33, 43, 91)),
-        muted: (rgb(152, 147, 165), hsl(257, 9, 61)),
-        subtle: (rgb(121, 117, 147), hsl(248, 12, 52)),
-        text: (rgb(87, 82, 121), hsl(248, 19, 40)),
-        love: (rgb(180, 99, 122), hsl(343, 35, 55)),
-        gold: (rgb(234, 157, 52), hsl(35, 81, 56)),
-        rose: (rgb(215, 130, 126), hsl(3, 53, 67)),
-        pine: (rgb(40, 105, 131), hsl(197, 53, 34)),
-        foam: (rgb(86, 148, 159), hsl(189, 30, 48)),
-        iris: (rgb(144, 122, 169), hsl(268, 21, 57)),
-        highlight_low: (rgb(244, 237, 232), hsl(25, 35, 93)),
-        highlight_med: (rgb(223, 218, 217), hsl(10, 9, 86)),
-        highlight_high: (rgb(206, 202, 205), hsl(315, 4, 80)),
+        base: rgb(250, 244, 237),
+        surface: rgb(255, 250, 243),
+        overlay: rgb(242, 233, 222),
+        muted: rgb(152, 147, 165),
+        subtle: rgb(121, 117, 147),
+        text: rgb(87, 82, 121),
+        love: rgb(180, 99, 122),
+        gold: rgb(234, 157, 52),
+        rose: rgb(215, 130, 126),
+        pine: rgb(40, 105, 131),
+        foam: rgb(86, 148, 159),
+        iris: rgb(144, 122, 169),
+        highlight_low: rgb(244, 237, 232),
+        highlight_med: rgb(223, 218, 217),
+        highlight_high: rgb(206, 202, 205),
     };
 
-    pub const fn get_role(&self, role: &Role) -> Colors {
+    /// Palettes of the previous major revision, see [`PaletteVersion::V1`](crate::PaletteVersion::V1)
+    pub const MAIN_V1: Palette = Palette {
+        base: rgb(25, 23, 36),
+        surface: rgb(31, 29, 46),
+        overlay: rgb(38, 35, 58),
+        muted: rgb(85, 81, 105),
+        subtle: rgb(110, 106, 134),
+        text: rgb(224, 222, 244),
+        love: rgb(235, 111, 146),
+        gold: rgb(246, 193, 119),
+        rose: rgb(235, 188, 186),
+        pine: rgb(49, 116, 143),
+        foam: rgb(156, 207, 216),
+        iris: rgb(196, 167, 231),
+        highlight_low: rgb(33, 31, 45),
+        highlight_med: rgb(42, 40, 55),
+        highlight_high: rgb(58, 56, 74),
+    };
+    pub const MOON_V1: Palette = Palette {
+        base: rgb(35, 33, 54),
+        surface: rgb(42, 39, 63),
+        overlay: rgb(57, 53, 82),
+        muted: rgb(89, 84, 109),
+        subtle: rgb(129, 124, 156),
+        text: rgb(224, 222, 244),
+        love: rgb(235, 111, 146),
+        gold: rgb(246, 193, 119),
+        rose: rgb(234, 154, 151),
+        pine: rgb(62, 143, 176),
+        foam: rgb(156, 207, 216),
+        iris: rgb(196, 167, 231),
+        highlight_low: rgb(42, 40, 61),
+        highlight_med: rgb(49, 47, 68),
+        highlight_high: rgb(63, 60, 83),
+    };
+    pub const DAWN_V1: Palette = Palette {
+        base: rgb(250, 244, 237),
+        surface: rgb(255, 250, 243),
+        overlay: rgb(242, 233, 222),
+        muted: rgb(152, 147, 165),
+        subtle: rgb(110, 106, 134),
+        text: rgb(87, 82, 121),
+        love: rgb(180, 99, 122),
+        gold: rgb(234, 157, 52),
+        rose: rgb(215, 130, 126),
+        pine: rgb(40, 105, 131),
+        foam: rgb(86, 148, 159),
+        iris: rgb(144, 122, 169),
+        highlight_low: rgb(242, 237, 233),
+        highlight_med: rgb(238, 233, 230),
+        highlight_high: rgb(228, 223, 222),
+    };
+
+    /// Builds a palette by computing the colors of every role
+    pub fn from_fn(colors: impl Fn(Role) -> Rgb) -> Palette {
+        Palette {
+            base: colors(Role::Base),
+            surface: colors(Role::Surface),
+            overlay: colors(Role::Overlay),
+            muted: colors(Role::Muted),
+            subtle: colors(Role::Subtle),
+            text: colors(Role::Text),
+            love: colors(Role::Love),
+            gold: colors(Role::Gold),
+            rose: colors(Role::Rose),
+            pine: colors(Role::Pine),
+            foam: colors(Role::Foam),
+            iris: colors(Role::Iris),
+            highlight_low: colors(Role::HighlightLow),
+            highlight_med: colors(Role::HighlightMed),
+            highlight_high: colors(Role::HighlightHigh),
+        }
+    }
+
+    pub const fn get_role(&self, role: &Role) -> Rgb {
         match role {
             Role::Base => self.base,
             Role::Surface => self.surface,
@@ -112,10 +192,10 @@ impl Palette {
     }
 
     pub const fn get_rgb(&self, role: &Role) -> Rgb {
-        self.get_role(role).0
+        self.get_role(role)
     }
     pub const fn get_hsl(&self, role: &Role) -> Hsl {
-        self.get_role(role).1
+        self.get_role(role).to_hsl()
     }
 }
 
@@ -128,45 +208,57 @@ impl Variant {
         }
     }
 
-    pub fn id(&self) -> String {
+    pub const fn id(&self) -> &'static str {
         match self {
             Self::Main => "rose-pine",
             Self::Moon => "rose-pine-moon",
             Self::Dawn => "rose-pine-dawn",
         }
-        .into()
     }
 
-    pub fn key(&self) -> String {
-        self.to_string().to_lowercase()
+    pub const fn key(&self) -> &'static str {
+        match self {
+            Self::Main => "main",
+            Self::Moon => "moon",
+            Self::Dawn => "dawn",
+        }
     }
 
-    pub fn name(&self) -> String {
+    pub const fn name(&self) -> &'static str {
         match self {
             Self::Main => "Rosé Pine",
             Self::Moon => "Rosé Pine Moon",
             Self::Dawn => "Rosé Pine Dawn",
         }
-        .into()
     }
 
-    pub fn kind(&self) -> VariantKind {
+    /// Finds a variant by its id, key or name ignoring case, e.g. `rose-pine-moon`, `moon` or `Rosé Pine Moon`
+    pub fn lookup(value: &str) -> Option<Variant> {
+        fn lowercase(s: &str) -> impl Iterator<Item = char> + '_ {
+            s.trim().chars().flat_map(char::to_lowercase)
+        }
+        Variant::iter().find(|v| {
+            [v.id(), v.key(), v.name()]
+                .iter()
+                .any(|s| lowercase(s).eq(lowercase(value)))
+        })
+    }
+
+    pub const fn kind(&self) -> VariantKind {
         match self {
             Self::Dawn => VariantKind::Light,
             _ => VariantKind::Dark,
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn metadata(&self) -> HashMap<String, String> {
-        Metadata::iter()
-            .map(|r| (r.to_string().to_snake_case(), r.format(self)))
-            .collect()
+        VariantDefinition::from(*self).metadata()
     }
 
+    #[cfg(feature = "std")]
     pub fn colors(&self) -> HashMap<String, Color> {
-        Role::iter()
-            .map(|r| (r.to_string().to_snake_case(), r.get_color(self)))
-            .collect()
+        VariantDefinition::from(*self).colors()
     }
 
     pub const fn get_rgb(&self, role: Role) -> Rgb {
@@ -181,6 +273,105 @@ impl Variant {
 const fn rgb(r: u8, g: u8, b: u8) -> Rgb {
     Rgb { r, g, b }
 }
-const fn hsl(h: u16, s: u8, l: u8) -> Hsl {
-    Hsl { h, s, l }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type PublishedHsl = [(u16, u8, u8); 15];
+
+    /// Hsl values as published on rosepinetheme.com, in the order of `Role::iter()`
+    const PUBLISHED_HSL: [(Variant, PublishedHsl); 3] = [
+        (
+            Variant::Main,
+            [
+                (249, 22, 12),
+                (247, 23, 15),
+                (248, 25, 18),
+                (249, 12, 47),
+                (248, 15, 61),
+                (245, 50, 91),
+                (343, 76, 68),
+                (35, 88, 72),
+                (2, 55, 83),
+                (197, 49, 38),
+                (189, 43, 73),
+                (267, 57, 78),
+                (244, 18, 15),
+                (249, 15, 28),
+                (248, 13, 36),
+            ],
+        ),
+        (
+            Variant::Moon,
+            [
+                (246, 24, 17),
+                (248, 24, 20),
+                (248, 21, 26),
+                (249, 12, 47),
+                (248, 15, 61),
+                (245, 50, 91),
+                (343, 76, 68),
+                (35, 88, 72),
+                (2, 66, 75),
+                (197, 48, 47),
+                (189, 43, 73),
+                (267, 57, 78),
+                (245, 22, 20),
+                (247, 16, 30),
+                (249, 15, 38),
+            ],
+        ),
+        (
+            Variant::Dawn,
+            [
+                (32, 57, 95),
+                (35, 100, 98),
+                (33, 43, 91),
+                (257, 9, 61),
+                (248, 12, 52),
+                (248, 19, 40),
+                (343, 35, 55),
+                (35, 81, 56),
+                (3, 53, 67),
+                (197, 53, 34),
+                (189, 30, 48),
+                (268, 21, 57),
+                (25, 35, 93),
+                (10, 9, 86),
+                (315, 4, 80),
+            ],
+        ),
+    ];
+
+    #[test]
+    fn derived_hsl_matches_published() {
+        for (variant, published) in PUBLISHED_HSL {
+            for (role, (h, s, l)) in Role::iter().zip(published) {
+                let hsl = variant.get_hsl(role);
+                let derived = (
+                    hsl.h.round() as u16,
+                    hsl.s.round() as u8,
+                    hsl.l.round() as u8,
+                );
+                assert_eq!(derived, (h, s, l), "{variant} {role}");
+            }
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(Variant::lookup("MOON"), Some(Variant::Moon));
+        assert_eq!(Variant::lookup(" rosé pine dawn"), Some(Variant::Dawn));
+        assert_eq!(Variant::lookup("rose-pine"), Some(Variant::Main));
+        assert_eq!(Variant::lookup("night"), None);
+    }
+
+    #[test]
+    fn const_derivation() {
+        const LOVE: Hsl = Palette::MOON.get_hsl(&Role::Love);
+        assert!((LOVE.h - 343.1).abs() < 0.05);
+        assert!((LOVE.s - 75.6).abs() < 0.05);
+        assert!((LOVE.l - 67.8).abs() < 0.05);
+    }
 }
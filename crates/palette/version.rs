@@ -0,0 +1,83 @@
+#[cfg(feature = "std")]
+use crate::PaletteFile;
+use crate::{Palette, Variant};
+use serde::Serialize;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Major revision of the built-in palettes, used to reproduce older releases
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, EnumIter, EnumString, Serialize)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteVersion {
+    /// Darker muted and subtle colors with flatter highlights, named inactive, subtle, highlight
+    /// inactive, highlight and highlight overlay at the time
+    V1,
+    /// The current palette
+    #[default]
+    V2,
+}
+
+impl PaletteVersion {
+    pub const fn palette(self, variant: Variant) -> Palette {
+        match (self, variant) {
+            (Self::V1, Variant::Main) => Palette::MAIN_V1,
+            (Self::V1, Variant::Moon) => Palette::MOON_V1,
+            (Self::V1, Variant::Dawn) => Palette::DAWN_V1,
+            (Self::V2, variant) => variant.get_palette(),
+        }
+    }
+}
+
+impl Variant {
+    pub const fn get_palette_version(&self, version: PaletteVersion) -> Palette {
+        version.palette(*self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl PaletteFile {
+    /// The built-in variants with the colors of a palette revision
+    pub fn builtin_version(version: PaletteVersion) -> Self {
+        let mut file = Self::builtin();
+        for variant in &mut file.variants {
+            variant.colors = version.palette(variant.slot());
+        }
+        file
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{Rgb, Role};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn versions() {
+        let v1 = PaletteFile::builtin_version(PaletteVersion::V1);
+        assert_eq!(
+            v1.variants[0].get_rgb(Role::Muted),
+            Rgb::new(0x55, 0x51, 0x69)
+        );
+        assert_eq!(
+            v1.variants[1].get_rgb(Role::Subtle),
+            Rgb::new(0x81, 0x7c, 0x9c)
+        );
+        assert_eq!(
+            v1.variants[2].get_rgb(Role::HighlightMed),
+            Rgb::new(0xee, 0xe9, 0xe6)
+        );
+        assert_eq!(
+            v1.variants[0].get_rgb(Role::Love),
+            Variant::Main.get_rgb(Role::Love)
+        );
+
+        let latest = PaletteFile::builtin_version(PaletteVersion::default());
+        for (definition, variant) in latest.variants.iter().zip(Variant::iter()) {
+            for role in Role::iter() {
+                assert_eq!(definition.get_rgb(role), variant.get_rgb(role));
+            }
+        }
+        assert_eq!("v1".parse(), Ok(PaletteVersion::V1));
+    }
+}
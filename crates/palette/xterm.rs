@@ -0,0 +1,160 @@
+use crate::{Color, DeltaE, Rgb};
+use serde::Serialize;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Indexed terminal palettes with the default xterm colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, EnumString, Serialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum XtermPalette {
+    /// The 16 ansi colors
+    Colors16,
+    /// A 4x4x4 color cube and 8 grays after the ansi colors
+    Colors88,
+    /// A 6x6x6 color cube and 24 grays after the ansi colors
+    Colors256,
+}
+
+/// Default xterm values of the 16 ansi colors, terminals and themes commonly change these
+pub const XTERM_ANSI: [Rgb; 16] = [
+    Rgb::new(0x00, 0x00, 0x00),
+    Rgb::new(0xcd, 0x00, 0x00),
+    Rgb::new(0x00, 0xcd, 0x00),
+    Rgb::new(0xcd, 0xcd, 0x00),
+    Rgb::new(0x00, 0x00, 0xee),
+    Rgb::new(0xcd, 0x00, 0xcd),
+    Rgb::new(0x00, 0xcd, 0xcd),
+    Rgb::new(0xe5, 0xe5, 0xe5),
+    Rgb::new(0x7f, 0x7f, 0x7f),
+    Rgb::new(0xff, 0x00, 0x00),
+    Rgb::new(0x00, 0xff, 0x00),
+    Rgb::new(0xff, 0xff, 0x00),
+    Rgb::new(0x5c, 0x5c, 0xff),
+    Rgb::new(0xff, 0x00, 0xff),
+    Rgb::new(0x00, 0xff, 0xff),
+    Rgb::new(0xff, 0xff, 0xff),
+];
+
+const CUBE_88: [u8; 4] = [0, 139, 205, 255];
+const GRAYS_88: [u8; 8] = [46, 92, 115, 139, 162, 185, 208, 231];
+const CUBE_256: [u8; 6] = [0, 95, 135, 175, 215, 255];
+const GRAYS_256: [u8; 24] = [
+    8, 18, 28, 38, 48, 58, 68, 78, 88, 98, 108, 118, 128, 138, 148, 158, 168, 178, 188, 198, 208,
+    218, 228, 238,
+];
+
+impl XtermPalette {
+    /// Number of colors in the palette
+    pub const fn size(self) -> usize {
+        match self {
+            Self::Colors16 => 16,
+            Self::Colors88 => 88,
+            Self::Colors256 => 256,
+        }
+    }
+
+    /// Color at `index`, `None` when the index is outside of the palette
+    pub fn get(self, index: u8) -> Option<Rgb> {
+        let index = index as usize;
+        if index >= self.size() {
+            return None;
+        }
+        if index < 16 {
+            return Some(XTERM_ANSI[index]);
+        }
+
+        let (levels, grays): (&[u8], &[u8]) = match self {
+            Self::Colors16 => unreachable!("index is within the ansi colors"),
+            Self::Colors88 => (&CUBE_88, &GRAYS_88),
+            Self::Colors256 => (&CUBE_256, &GRAYS_256),
+        };
+        let n = levels.len();
+        let i = index - 16;
+        Some(match i.checked_sub(n * n * n) {
+            Some(gray) => Rgb::new(grays[gray], grays[gray], grays[gray]),
+            None => Rgb::new(levels[i / (n * n)], levels[i / n % n], levels[i % n]),
+        })
+    }
+
+    /// Indices a color is quantized to, the 88 and 256 color palettes skip the ansi colors
+    /// as their actual values depend on the terminal
+    fn candidates(self) -> std::ops::Range<usize> {
+        match self {
+            Self::Colors16 => 0..16,
+            _ => 16..self.size(),
+        }
+    }
+}
+
+impl Rgb {
+    /// Index of the perceptually nearest color in an xterm palette using cie ΔE 2000
+    pub fn xterm_index(self, palette: XtermPalette) -> u8 {
+        self.xterm_index_by(palette, DeltaE::default())
+    }
+
+    pub fn xterm_index_by(self, palette: XtermPalette, metric: DeltaE) -> u8 {
+        palette
+            .candidates()
+            .map(|i| i as u8)
+            .map(|i| (i, metric.distance(self, palette.get(i).unwrap())))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+            .unwrap()
+    }
+}
+
+impl Color {
+    pub fn xterm_index(&self, palette: XtermPalette) -> u8 {
+        self.rgb.xterm_index(palette)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Role, Variant};
+
+    #[test]
+    fn palettes() {
+        let p = XtermPalette::Colors256;
+        assert_eq!(p.get(1), Some(Rgb::new(0xcd, 0, 0)));
+        assert_eq!(p.get(16), Some(Rgb::new(0, 0, 0)));
+        assert_eq!(p.get(196), Some(Rgb::new(255, 0, 0)));
+        assert_eq!(p.get(110), Some(Rgb::new(135, 175, 215)));
+        assert_eq!(p.get(231), Some(Rgb::new(255, 255, 255)));
+        assert_eq!(p.get(232), Some(Rgb::new(8, 8, 8)));
+        assert_eq!(p.get(255), Some(Rgb::new(238, 238, 238)));
+
+        let p = XtermPalette::Colors88;
+        assert_eq!(p.get(79), Some(Rgb::new(255, 255, 255)));
+        assert_eq!(p.get(80), Some(Rgb::new(46, 46, 46)));
+        assert_eq!(p.get(87), Some(Rgb::new(231, 231, 231)));
+        assert_eq!(p.get(88), None);
+        assert_eq!(XtermPalette::Colors16.get(16), None);
+    }
+
+    #[test]
+    fn exact_colors() {
+        for palette in [XtermPalette::Colors88, XtermPalette::Colors256] {
+            for i in palette.candidates().map(|i| i as u8) {
+                let color = palette.get(i).unwrap();
+                assert_eq!(palette.get(color.xterm_index(palette)), Some(color));
+            }
+        }
+    }
+
+    #[test]
+    fn quantize() {
+        let love = Variant::Moon.get_rgb(Role::Love);
+        assert_eq!(love.xterm_index(XtermPalette::Colors256), 204);
+        assert_eq!(love.xterm_index(XtermPalette::Colors88), 65);
+        assert_eq!(love.xterm_index(XtermPalette::Colors16), 13);
+
+        let base = Variant::Main.get_rgb(Role::Base);
+        assert_eq!(base.xterm_index(XtermPalette::Colors256), 234);
+        assert_eq!(base.xterm_index(XtermPalette::Colors16), 0);
+
+        let text = Variant::Dawn.get_rgb(Role::Text);
+        assert_eq!(text.xterm_index(XtermPalette::Colors256), 60);
+    }
+}
@@ -0,0 +1,461 @@
+//! Python module `rose_pine`, built into an abi3 wheel with maturin
+
+use anyhow::Context;
+use clap::ValueEnum;
+use palette::{ContrastTargets, Deficiency, PaletteVersion};
+use pyo3::{
+    exceptions::{PyOSError, PyValueError},
+    prelude::*,
+    types::PyDict,
+};
+use rose_pine_build::{
+    generate_template, Config, Delimiter, Format, Options, ParseOptions, TemplateOptions,
+};
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+/// Declares a python enum mirroring one of the palette enums along with conversions between them
+macro_rules! mirror_enum {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        #[pyclass(eq, eq_int, frozen, hash, module = "rose_pine")]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant),*
+        }
+
+        impl From<$name> for palette::$name {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => Self::$variant),*
+                }
+            }
+        }
+
+        impl From<palette::$name> for $name {
+            fn from(value: palette::$name) -> Self {
+                match value {
+                    $(palette::$name::$variant => Self::$variant),*
+                }
+            }
+        }
+    };
+}
+
+mirror_enum!(Variant { Main, Moon, Dawn });
+
+mirror_enum!(Role {
+    Base,
+    Surface,
+    Overlay,
+    Muted,
+    Subtle,
+    Text,
+    Love,
+    Gold,
+    Rose,
+    Pine,
+    Foam,
+    Iris,
+    HighlightLow,
+    HighlightMed,
+    HighlightHigh,
+});
+
+#[pymethods]
+impl Variant {
+    /// Finds a variant by its id, key or name ignoring case
+    #[staticmethod]
+    fn parse(value: &str) -> PyResult<Self> {
+        palette::Variant::lookup(value)
+            .map(Self::from)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown variant `{value}`")))
+    }
+
+    #[getter]
+    fn id(&self) -> &'static str {
+        palette::Variant::from(*self).id()
+    }
+
+    #[getter]
+    fn key(&self) -> &'static str {
+        palette::Variant::from(*self).key()
+    }
+
+    #[getter]
+    fn name(&self) -> &'static str {
+        palette::Variant::from(*self).name()
+    }
+
+    /// `dark` or `light`
+    #[getter]
+    fn kind(&self) -> String {
+        palette::Variant::from(*self).kind().to_string()
+    }
+
+    fn color(&self, role: Role) -> Color {
+        Color(palette::Role::from(role).get_color(&(*self).into()))
+    }
+
+    /// Colors of every role by its snake_case name, e.g. `highlight_med`
+    fn colors(&self) -> HashMap<String, Color> {
+        palette::Variant::from(*self)
+            .colors()
+            .into_iter()
+            .map(|(role, color)| (role, Color(color)))
+            .collect()
+    }
+
+    fn __str__(&self) -> &'static str {
+        self.key()
+    }
+}
+
+#[pymethods]
+impl Role {
+    /// Finds a role by its snake_case name, e.g. `highlight_med`
+    #[staticmethod]
+    fn parse(value: &str) -> PyResult<Self> {
+        value
+            .parse::<palette::Role>()
+            .map(Self::from)
+            .map_err(|_| PyValueError::new_err(format!("unknown role `{value}`")))
+    }
+
+    /// `background`, `foreground`, `accent` or `highlight`
+    #[getter]
+    fn category(&self) -> String {
+        palette::Role::from(*self).category().to_string()
+    }
+
+    fn __str__(&self) -> String {
+        palette::Role::from(*self).to_string()
+    }
+}
+
+/// A color with all of its representations, created from a role or parsed from any css color
+#[pyclass(frozen, module = "rose_pine")]
+#[derive(Debug, Clone)]
+pub struct Color(palette::Color);
+
+#[pymethods]
+impl Color {
+    #[new]
+    fn new(value: &str) -> PyResult<Self> {
+        palette::Color::parse(value)
+            .map(Self)
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// Hex digits without a leading `#`
+    #[getter]
+    fn hex(&self) -> &str {
+        &self.0.hex
+    }
+
+    #[getter]
+    fn rgb(&self) -> (u8, u8, u8) {
+        self.0.rgb.into()
+    }
+
+    #[getter]
+    fn hsl(&self) -> (f32, f32, f32) {
+        let hsl = self.0.hsl;
+        (hsl.h, hsl.s, hsl.l)
+    }
+
+    #[getter]
+    fn hsv(&self) -> (f32, f32, f32) {
+        let hsv = self.0.hsv;
+        (hsv.h, hsv.s, hsv.v)
+    }
+
+    #[getter]
+    fn hwb(&self) -> (f32, f32, f32) {
+        let hwb = self.0.hwb;
+        (hwb.h, hwb.w, hwb.b)
+    }
+
+    #[getter]
+    fn lab(&self) -> (f32, f32, f32) {
+        let lab = self.0.lab;
+        (lab.l, lab.a, lab.b)
+    }
+
+    #[getter]
+    fn lch(&self) -> (f32, f32, f32) {
+        let lch = self.0.lch;
+        (lch.l, lch.c, lch.h)
+    }
+
+    #[getter]
+    fn oklab(&self) -> (f32, f32, f32) {
+        let oklab = self.0.oklab;
+        (oklab.l, oklab.a, oklab.b)
+    }
+
+    #[getter]
+    fn oklch(&self) -> (f32, f32, f32) {
+        let oklch = self.0.oklch;
+        (oklch.l, oklch.c, oklch.h)
+    }
+
+    /// Opacity percentage of a parsed color, `None` when opaque
+    #[getter]
+    fn alpha(&self) -> Option<f32> {
+        self.0.alpha
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.0.rgb == other.0.rgb && self.0.alpha == other.0.alpha
+    }
+
+    fn __str__(&self) -> String {
+        format!("#{}", self.0.hex)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Color('#{}')", self.0.hex)
+    }
+}
+
+/// Errors of reading files become an `OSError`, invalid options and templates a `ValueError`
+fn to_py_err(err: anyhow::Error) -> PyErr {
+    match err.root_cause().is::<std::io::Error>() {
+        true => PyOSError::new_err(format!("{err:#}")),
+        false => PyValueError::new_err(format!("{err:#}")),
+    }
+}
+
+/// Parses the value of a command line option, accepting `rgb_function` as well as `rgb-function`
+fn parse_value<T: ValueEnum>(kind: &str, value: &str) -> PyResult<T> {
+    T::from_str(&value.replace('_', "-"), true)
+        .map_err(|_| PyValueError::new_err(format!("unknown {kind} `{value}`")))
+}
+
+/// `name=value` definitions from a dict of strings, in insertion order since roles can refer to earlier ones
+fn definitions(dict: Option<&Bound<'_, PyDict>>) -> PyResult<Vec<String>> {
+    dict.into_iter()
+        .flatten()
+        .map(|(name, value)| {
+            let (name, value) = (name.extract::<String>()?, value.extract::<String>()?);
+            Ok(format!("{name}={value}"))
+        })
+        .collect()
+}
+
+/// Renders a template file for every variant and returns the results by variant key.
+/// Keyword arguments are the template options of `rose-pine-build`, e.g.
+/// `render("template.json", format="hsl", tera=True, roles={"brand": "#c4a7e7"})`.
+#[pyfunction]
+#[pyo3(signature = (
+    template,
+    *,
+    format = None,
+    precision = 0,
+    force_alpha = false,
+    flatten = None,
+    prefix = '$',
+    separator = '|',
+    delimiter = None,
+    tera = false,
+    palette = None,
+    palette_version = None,
+    roles = None,
+    aliases = None,
+    interpolate = None,
+    high_contrast = false,
+    contrast_targets = None,
+    simulate = None,
+))]
+#[allow(clippy::too_many_arguments)]
+fn render(
+    template: PathBuf,
+    format: Option<&str>,
+    precision: usize,
+    force_alpha: bool,
+    flatten: Option<Role>,
+    prefix: char,
+    separator: char,
+    delimiter: Option<&str>,
+    tera: bool,
+    palette: Option<PathBuf>,
+    palette_version: Option<&str>,
+    roles: Option<&Bound<'_, PyDict>>,
+    aliases: Option<&Bound<'_, PyDict>>,
+    interpolate: Option<&Bound<'_, PyDict>>,
+    high_contrast: bool,
+    contrast_targets: Option<HashMap<String, f32>>,
+    simulate: Option<Vec<String>>,
+) -> PyResult<HashMap<String, String>> {
+    let palette_version = palette_version
+        .map(|version| {
+            PaletteVersion::from_str(version)
+                .map_err(|_| PyValueError::new_err(format!("unknown palette version `{version}`")))
+        })
+        .transpose()?;
+    let high_contrast = match high_contrast {
+        false if contrast_targets.is_some() => {
+            return Err(PyValueError::new_err(
+                "`contrast_targets` only apply with `high_contrast=True`",
+            ))
+        }
+        true => {
+            let mut targets = ContrastTargets::default();
+            for (group, ratio) in contrast_targets.unwrap_or_default() {
+                targets.set(&group, ratio).map_err(PyValueError::new_err)?;
+            }
+            Some(targets)
+        }
+        false => None,
+    };
+    let simulate = simulate
+        .unwrap_or_default()
+        .iter()
+        .map(|deficiency| {
+            Deficiency::from_str(deficiency)
+                .map_err(|_| PyValueError::new_err(format!("unknown deficiency `{deficiency}`")))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let options = TemplateOptions {
+        tera,
+        parse: ParseOptions {
+            prefix,
+            seperator: separator,
+            delimiter: delimiter
+                .map_or(Ok(Delimiter::default()), |d| parse_value("delimiter", d))?,
+        },
+        generate: Options {
+            format: format.map_or(Ok(Format::default()), |f| parse_value("format", f))?,
+            strip_spaces: false,
+            force_alpha,
+            precision,
+            flatten: flatten.map(palette::Role::from),
+        },
+        palette,
+        palette_version,
+        aliases: definitions(aliases)?,
+        interpolate: definitions(interpolate)?,
+        roles: definitions(roles)?,
+        high_contrast,
+        simulate,
+    };
+    let config = Config::try_from(options).map_err(to_py_err)?;
+
+    let variants = generate_template(&template, &config)
+        .with_context(|| format!("unable to render template at path {template:?}"))
+        .map_err(to_py_err)?;
+
+    Ok(variants
+        .into_iter()
+        .map(|(variant, content)| (variant.key.clone(), content))
+        .collect())
+}
+
+#[pymodule]
+#[pyo3(name = "rose_pine")]
+fn rose_pine_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Variant>()?;
+    m.add_class::<Role>()?;
+    m.add_class::<Color>()?;
+    m.add_function(wrap_pyfunction!(render, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::exceptions::PyTypeError;
+    use std::{fs, path::Path};
+    use strum::IntoEnumIterator;
+
+    /// Writes a template into the temp dir, unique per test since tests run in parallel
+    fn template(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rose-pine-python-{name}"));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// Calls `render` like python code would, keyword arguments included
+    fn call_render(
+        path: &Path,
+        kwargs: impl for<'py> FnOnce(&Bound<'py, PyDict>) -> PyResult<()>,
+    ) -> PyResult<HashMap<String, String>> {
+        Python::initialize();
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            kwargs(&dict)?;
+            wrap_pyfunction!(render, py)?
+                .call((path,), Some(&dict))?
+                .extract()
+        })
+    }
+
+    #[test]
+    fn mirrored_enums() {
+        for role in palette::Role::iter() {
+            assert_eq!(palette::Role::from(Role::from(role)), role);
+        }
+        for variant in palette::Variant::iter() {
+            assert_eq!(palette::Variant::from(Variant::from(variant)), variant);
+        }
+    }
+
+    #[test]
+    fn render_tera_roles() {
+        let path = template("roles.txt", "{{ brand.hex }} {{ search.alpha }}");
+        let variants = call_render(&path, |kwargs| {
+            let roles = PyDict::new(kwargs.py());
+            roles.set_item("brand", "$iris")?;
+            kwargs.set_item("tera", true)?;
+            kwargs.set_item("roles", roles)
+        })
+        .unwrap();
+
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants["main"], "c4a7e7 20");
+        assert_eq!(variants["dawn"], "907aa9 20");
+    }
+
+    #[test]
+    fn render_palette_version() {
+        let path = template("version.txt", "$muted");
+        let variants = call_render(&path, |kwargs| kwargs.set_item("palette_version", "v1"));
+        assert_eq!(variants.unwrap()["main"], "#555169");
+
+        let err = call_render(&path, |kwargs| kwargs.set_item("palette_version", "v3"));
+        Python::attach(|py| assert!(err.unwrap_err().is_instance_of::<PyValueError>(py)));
+    }
+
+    #[test]
+    fn render_contrast_targets() {
+        let path = template("contrast.txt", "$key");
+        let targets = |kwargs: &Bound<'_, PyDict>| {
+            let targets = PyDict::new(kwargs.py());
+            targets.set_item("accent", 7.0)?;
+            kwargs.set_item("contrast_targets", targets)
+        };
+
+        let variants = call_render(&path, |kwargs| {
+            kwargs.set_item("high_contrast", true)?;
+            targets(kwargs)
+        });
+        assert_eq!(
+            variants.unwrap()["moon-high-contrast"],
+            "moon-high-contrast"
+        );
+
+        let err = call_render(&path, targets).unwrap_err();
+        Python::attach(|py| assert!(err.is_instance_of::<PyValueError>(py)));
+    }
+
+    #[test]
+    fn render_errors() {
+        let path = template("errors.txt", "$love");
+        let err = call_render(&path, |kwargs| kwargs.set_item("colour", "hex")).unwrap_err();
+        Python::attach(|py| assert!(err.is_instance_of::<PyTypeError>(py)));
+
+        let missing = std::env::temp_dir().join("rose-pine-python-missing.txt");
+        let err = call_render(&missing, |_| Ok(())).unwrap_err();
+        Python::attach(|py| assert!(err.is_instance_of::<PyOSError>(py)));
+    }
+}